@@ -0,0 +1,354 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use pumpkin::plugin::{BoxFuture, Cancellable, EventHandler};
+use pumpkin::plugin::events::player::player_chat::PlayerChatEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::commands::{load_socials, socials_path};
+use crate::config::{AdvertAction, Config};
+
+pub const PERMISSION_ADVERT_BYPASS_URL: &str = "JinxCore:advert.bypass.url";
+pub const PERMISSION_ADVERT_BYPASS_DOMAIN: &str = "JinxCore:advert.bypass.domain";
+pub const PERMISSION_ADVERT_BYPASS_IP: &str = "JinxCore:advert.bypass.ip";
+pub const PERMISSION_ADVERT_BYPASS_INVITE: &str = "JinxCore:advert.bypass.invite";
+
+const INVITE_PREFIXES: [&str; 4] = [
+    "discord.gg/",
+    "discord.com/invite/",
+    "discordapp.com/invite/",
+    "discord.me/",
+];
+
+const DOT_WORDS: [&str; 8] = [
+    "dot", "(dot)", "[dot]", "{dot}", ".", "(.)", "[.]", "{.}",
+];
+
+const TOP_LEVEL_DOMAINS: [&str; 48] = [
+    "com", "net", "org", "gg", "io", "me", "co", "uk", "de", "us", "eu", "fr", "nl", "ru",
+    "pl", "es", "it", "ca", "au", "br", "se", "no", "dk", "fi", "be", "at", "ch", "cz",
+    "xyz", "tk", "ml", "ga", "cf", "gq", "club", "online", "site", "fun", "pro", "info",
+    "biz", "dev", "app", "tv", "cc", "to", "ly", "host",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Detection {
+    Url,
+    Domain,
+    Ip,
+    Invite,
+}
+
+impl Detection {
+    fn action(self, config: &Config) -> AdvertAction {
+        match self {
+            Detection::Url => config.advert_url_action,
+            Detection::Domain => config.advert_domain_action,
+            Detection::Ip => config.advert_ip_action,
+            Detection::Invite => config.advert_invite_action,
+        }
+    }
+
+    fn bypass_permission(self) -> &'static str {
+        match self {
+            Detection::Url => PERMISSION_ADVERT_BYPASS_URL,
+            Detection::Domain => PERMISSION_ADVERT_BYPASS_DOMAIN,
+            Detection::Ip => PERMISSION_ADVERT_BYPASS_IP,
+            Detection::Invite => PERMISSION_ADVERT_BYPASS_INVITE,
+        }
+    }
+}
+
+struct Finding {
+    start: usize,
+    end: usize,
+    kind: Detection,
+    host: String,
+}
+
+/// Hosts and invite codes that are never flagged: the links in `socials.yml` plus
+/// `advert_allowed_domains`. Built on load and on `/jinx reload`.
+#[derive(Default)]
+pub struct Allowlist {
+    hosts: HashSet<String>,
+    invites: HashSet<String>,
+}
+
+impl Allowlist {
+    pub fn load(data_dir: &Path, extra: &[String]) -> Self {
+        let mut allowlist = Self::default();
+        let links = load_socials(&socials_path(data_dir))
+            .map(|socials| socials.into_values().collect::<Vec<_>>())
+            .unwrap_or_default();
+        for link in links.iter().chain(extra.iter()) {
+            let normalized = strip_scheme(&link.trim().to_lowercase());
+            if let Some(code) = invite_code(&normalized) {
+                allowlist.invites.insert(code);
+                continue;
+            }
+            let host = host_of(&normalized);
+            if !host.is_empty() {
+                allowlist.hosts.insert(host);
+            }
+        }
+        allowlist
+    }
+
+    fn allows(&self, finding: &Finding) -> bool {
+        match finding.kind {
+            Detection::Invite => self.invites.contains(&finding.host),
+            Detection::Ip => self.hosts.contains(&finding.host),
+            Detection::Url | Detection::Domain => self.hosts.iter().any(|allowed| {
+                finding.host == *allowed || finding.host.ends_with(&format!(".{allowed}"))
+            }),
+        }
+    }
+}
+
+pub type AdvertAllowlist = Arc<RwLock<Allowlist>>;
+
+pub struct AdvertisingHandler {
+    config: Arc<RwLock<Config>>,
+    allowlist: AdvertAllowlist,
+}
+
+impl AdvertisingHandler {
+    pub fn new(config: Arc<RwLock<Config>>, allowlist: AdvertAllowlist) -> Self {
+        Self { config, allowlist }
+    }
+
+    fn scan(tokens: &[&str]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let mut index = 0;
+        while index < tokens.len() {
+            let mut candidate = normalize_token(tokens[index]);
+            let mut end = index;
+            while end + 2 < tokens.len() && is_dot_word(tokens[end + 1]) {
+                candidate.push('.');
+                candidate.push_str(&normalize_token(tokens[end + 2]));
+                end += 2;
+            }
+
+            if end > index {
+                if let Some((kind, host)) = classify(&candidate) {
+                    findings.push(Finding {
+                        start: index,
+                        end,
+                        kind,
+                        host,
+                    });
+                    index = end + 1;
+                    continue;
+                }
+            }
+
+            if let Some((kind, host)) = classify(&normalize_token(tokens[index])) {
+                findings.push(Finding {
+                    start: index,
+                    end: index,
+                    kind,
+                    host,
+                });
+            }
+            index += 1;
+        }
+        findings
+    }
+}
+
+fn is_dot_word(token: &str) -> bool {
+    let lower = token.to_lowercase();
+    DOT_WORDS.contains(&lower.as_str())
+}
+
+fn normalize_token(token: &str) -> String {
+    let mut normalized = token.to_lowercase();
+    for obfuscated in ["(dot)", "[dot]", "{dot}", "(.)", "[.]", "{.}"] {
+        normalized = normalized.replace(obfuscated, ".");
+    }
+    normalized
+        .trim_matches(|c: char| matches!(c, ',' | ';' | '!' | '?' | '"' | '\'' | '(' | ')' | '<' | '>' | '[' | ']'))
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn strip_scheme(value: &str) -> String {
+    let value = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+        .unwrap_or(value);
+    value.strip_prefix("www.").unwrap_or(value).to_string()
+}
+
+fn host_of(value: &str) -> String {
+    let host = value.split(['/', '?', '#']).next().unwrap_or_default();
+    host.split(':').next().unwrap_or_default().to_string()
+}
+
+fn invite_code(value: &str) -> Option<String> {
+    INVITE_PREFIXES.iter().find_map(|prefix| {
+        let index = value.find(prefix)?;
+        let code = value[index + prefix.len()..]
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or_default();
+        if code.is_empty() {
+            None
+        } else {
+            Some(code.to_string())
+        }
+    })
+}
+
+fn is_ipv4(value: &str) -> bool {
+    let (address, port) = match value.split_once(':') {
+        Some((address, port)) => (address, Some(port)),
+        None => (value, None),
+    };
+    if let Some(port) = port {
+        if port.is_empty() || port.parse::<u16>().is_err() {
+            return false;
+        }
+    }
+    let octets = address.split('.').collect::<Vec<_>>();
+    octets.len() == 4
+        && octets.iter().all(|octet| {
+            !octet.is_empty() && octet.len() <= 3 && octet.parse::<u8>().is_ok()
+        })
+}
+
+fn is_domain(value: &str) -> bool {
+    let labels = value.split('.').collect::<Vec<_>>();
+    if labels.len() < 2 {
+        return false;
+    }
+    let Some(tld) = labels.last() else {
+        return false;
+    };
+    if !TOP_LEVEL_DOMAINS.contains(tld) {
+        return false;
+    }
+    labels.iter().all(|label| {
+        !label.is_empty()
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+fn classify(candidate: &str) -> Option<(Detection, String)> {
+    if candidate.is_empty() {
+        return None;
+    }
+    if let Some(code) = invite_code(candidate) {
+        return Some((Detection::Invite, code));
+    }
+
+    let has_scheme = candidate.starts_with("http://")
+        || candidate.starts_with("https://")
+        || candidate.starts_with("www.");
+    let stripped = strip_scheme(candidate);
+    let host = host_of(&stripped);
+    let host_with_port = stripped
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default();
+
+    if is_ipv4(host_with_port) {
+        return Some((Detection::Ip, host));
+    }
+    if has_scheme && !host.is_empty() {
+        return Some((Detection::Url, host));
+    }
+    if is_domain(&host) {
+        let kind = if stripped.len() > host_with_port.len() {
+            Detection::Url
+        } else {
+            Detection::Domain
+        };
+        return Some((kind, host));
+    }
+    None
+}
+
+impl EventHandler<PlayerChatEvent> for AdvertisingHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerChatEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+
+            if !config.advert_enabled || event.cancelled() {
+                return;
+            }
+
+            let tokens = event.message.split_whitespace().collect::<Vec<_>>();
+            let findings = Self::scan(&tokens);
+            if findings.is_empty() {
+                return;
+            }
+
+            let mut cancel = false;
+            let mut replaced = Vec::new();
+            for finding in &findings {
+                let action = finding.kind.action(&config);
+                let allowed = self.allowlist.read().unwrap().allows(finding);
+                if action == AdvertAction::Allow || allowed {
+                    continue;
+                }
+                if event
+                    .player
+                    .has_permission(finding.kind.bypass_permission())
+                    .await
+                {
+                    continue;
+                }
+                match action {
+                    AdvertAction::Cancel => cancel = true,
+                    AdvertAction::Replace => replaced.push((finding.start, finding.end)),
+                    AdvertAction::Allow => {}
+                }
+            }
+
+            if cancel {
+                event.set_cancelled(true);
+                if !config.advert_notify_message.is_empty() {
+                    let message = TextComponent::text(config.advert_notify_message.clone())
+                        .color_named(NamedColor::Red);
+                    event.player.send_system_message(&message).await;
+                }
+                return;
+            }
+
+            if replaced.is_empty() {
+                return;
+            }
+
+            let mut output = Vec::new();
+            let mut index = 0;
+            while index < tokens.len() {
+                if let Some((_, end)) = replaced.iter().find(|(start, _)| *start == index) {
+                    output.push(config.advert_replacement.as_str());
+                    index = end + 1;
+                } else {
+                    output.push(tokens[index]);
+                    index += 1;
+                }
+            }
+            event.message = output.join(" ");
+
+            if !config.advert_notify_message.is_empty() {
+                let message = TextComponent::text(config.advert_notify_message.clone())
+                    .color_named(NamedColor::Yellow);
+                event.player.send_system_message(&message).await;
+            }
+        })
+    }
+}
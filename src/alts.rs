@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use pumpkin::plugin::{BoxFuture, EventHandler};
+use pumpkin::plugin::events::player::player_join::PlayerJoinEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use uuid::Uuid;
+
+use crate::bans::{BanEntry, BanKind};
+use crate::config::Config;
+use crate::seen::{SeenEntry, unix_now};
+use crate::staff::notify_staff;
+use crate::{PluginState, branding};
+
+pub const PERMISSION_ALTS_ALERT: &str = "JinxCore:alts.alert";
+
+const SALT_FILE_NAME: &str = "address.salt";
+
+pub struct AltAccount {
+    pub uuid: Uuid,
+    pub name: String,
+    pub shared_addresses: usize,
+    pub banned: bool,
+    pub muted: bool,
+}
+
+impl AltAccount {
+    pub fn flags(&self) -> String {
+        let mut flags = Vec::new();
+        if self.banned {
+            flags.push("banned");
+        }
+        if self.muted {
+            flags.push("muted");
+        }
+        if flags.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", flags.join(", "))
+        }
+    }
+}
+
+/// Reads the per-install salt used for hashed addresses, creating it on first use.
+pub fn load_or_create_salt(data_dir: &Path) -> String {
+    let path = data_dir.join(SALT_FILE_NAME);
+    if let Ok(existing) = fs::read_to_string(&path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return existing.to_string();
+        }
+    }
+    let salt = Uuid::new_v4().simple().to_string();
+    if let Err(err) = fs::write(&path, &salt) {
+        log::warn!("Failed to write {SALT_FILE_NAME}: {err}");
+    }
+    salt
+}
+
+/// Returns the form an address is stored in, hashing it when the privacy option is enabled.
+pub fn address_key(config: &Config, salt: &str, ip: &str) -> String {
+    if !config.alts_hash_addresses {
+        return ip.to_string();
+    }
+    let digest = Uuid::new_v3(&Uuid::NAMESPACE_OID, format!("{salt}:{ip}").as_bytes());
+    format!("hash:{}", digest.simple())
+}
+
+fn is_banned(bans: &[BanEntry], entry: &SeenEntry, now: u64) -> bool {
+    bans.iter().filter(|ban| !ban.is_expired(now)).any(|ban| match ban.kind {
+        BanKind::Player => ban.uuid == Some(entry.uuid),
+        BanKind::Ip => ban
+            .address
+            .as_deref()
+            .is_some_and(|address| entry.has_address(address)),
+    })
+}
+
+fn addresses_of(entry: &SeenEntry) -> Vec<String> {
+    let mut addresses = entry
+        .addresses
+        .iter()
+        .map(|record| record.address.clone())
+        .collect::<Vec<_>>();
+    if let Some(last) = &entry.last_address {
+        if !addresses.contains(last) {
+            addresses.push(last.clone());
+        }
+    }
+    addresses
+}
+
+/// Lists every other account that has used one of the given addresses.
+pub fn find_alts(state: &PluginState, uuid: Uuid, addresses: &[String]) -> Vec<AltAccount> {
+    let now = unix_now();
+    let seen = state.seen.read().unwrap();
+    let bans = state.bans.read().unwrap();
+    let mutes = state.mutes.read().unwrap();
+
+    let mut alts = seen
+        .values()
+        .filter(|entry| entry.uuid != uuid)
+        .filter_map(|entry| {
+            let shared_addresses = addresses
+                .iter()
+                .filter(|address| entry.has_address(address))
+                .count();
+            if shared_addresses == 0 {
+                return None;
+            }
+            Some(AltAccount {
+                uuid: entry.uuid,
+                name: entry.name.clone(),
+                shared_addresses,
+                banned: is_banned(&bans, entry, now),
+                muted: mutes
+                    .get(&entry.uuid)
+                    .is_some_and(|mute| !mute.is_expired(now)),
+            })
+        })
+        .collect::<Vec<_>>();
+    alts.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    alts
+}
+
+pub fn known_addresses(seen: &HashMap<Uuid, SeenEntry>, uuid: Uuid) -> Vec<String> {
+    seen.get(&uuid).map(addresses_of).unwrap_or_default()
+}
+
+pub struct AltJoinHandler {
+    state: Arc<PluginState>,
+}
+
+impl AltJoinHandler {
+    pub fn new(state: Arc<PluginState>) -> Self {
+        Self { state }
+    }
+}
+
+impl EventHandler<PlayerJoinEvent> for AltJoinHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        server: &'a Arc<Server>,
+        event: &'a mut PlayerJoinEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let config = {
+                let guard = self.state.config.read().unwrap();
+                guard.clone()
+            };
+            if !config.alts_join_alert {
+                return;
+            }
+            if self.state.join_flood.read().unwrap().is_kicked(event.player.gameprofile.id) {
+                return;
+            }
+
+            let ip = event.player.client.address().await.ip().to_string();
+            let address = address_key(&config, &self.state.address_salt, &ip);
+            let flagged = find_alts(&self.state, event.player.gameprofile.id, &[address])
+                .into_iter()
+                .filter(|alt| alt.banned || alt.muted)
+                .collect::<Vec<_>>();
+            if flagged.is_empty() {
+                return;
+            }
+
+            let accounts = flagged
+                .iter()
+                .map(|alt| format!("{}{}", alt.name, alt.flags()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let message = branding::brand(
+                TextComponent::text(format!(
+                    "{} joined from an address shared with: {accounts}",
+                    event.player.gameprofile.name
+                ))
+                .color_named(NamedColor::Gold),
+            );
+            notify_staff(server, PERMISSION_ALTS_ALERT, &message).await;
+        })
+    }
+}
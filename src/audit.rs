@@ -0,0 +1,324 @@
+use std::cell::Cell;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs},
+};
+use pumpkin::server::Server;
+
+use crate::config::Config;
+use crate::discord_bot::{DiscordBridge, DiscordRoute, escape_markdown, send_discord_message};
+use crate::seen::{format_date, unix_now};
+
+const AUDIT_FILE_NAME: &str = "audit.log";
+
+/// Argument names whose values identify the player a command acted on.
+const TARGET_ARGS: [&str; 2] = ["player", "target"];
+
+#[derive(Clone, Debug)]
+pub struct AuditRecord {
+    pub time: u64,
+    pub actor: String,
+    pub command: String,
+    pub targets: Vec<String>,
+    pub details: String,
+    pub world: Option<String>,
+    pub position: Option<String>,
+    pub success: bool,
+}
+
+impl AuditRecord {
+    fn to_line(&self) -> String {
+        [
+            self.time.to_string(),
+            clean_field(&self.actor),
+            clean_field(&self.command),
+            clean_field(&self.targets.join(",")),
+            clean_field(&self.details),
+            self.world.clone().unwrap_or_default(),
+            self.position.clone().unwrap_or_default(),
+            if self.success { "ok" } else { "failed" }.to_string(),
+        ]
+        .join("\t")
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        if fields.len() != 8 {
+            return None;
+        }
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        Some(Self {
+            time: fields[0].parse().ok()?,
+            actor: fields[1].to_string(),
+            command: fields[2].to_string(),
+            targets: fields[3]
+                .split(',')
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+            details: fields[4].to_string(),
+            world: optional(fields[5]),
+            position: optional(fields[6]),
+            success: fields[7] == "ok",
+        })
+    }
+
+    pub fn involves(&self, name: &str) -> bool {
+        self.actor.eq_ignore_ascii_case(name)
+            || self
+                .targets
+                .iter()
+                .any(|target| target.eq_ignore_ascii_case(name))
+    }
+
+    /// Human-readable form used by `/jinx audit` and the Discord mirror.
+    pub fn summary(&self) -> String {
+        let mut line = format!("{} {} /{}", format_date(self.time), self.actor, self.command);
+        if !self.targets.is_empty() {
+            line.push_str(&format!(" -> {}", self.targets.join(", ")));
+        }
+        if !self.details.is_empty() {
+            line.push_str(&format!(" [{}]", self.details));
+        }
+        if let Some(world) = &self.world {
+            line.push_str(&format!(" @ {world}"));
+            if let Some(position) = &self.position {
+                line.push_str(&format!(" {position}"));
+            }
+        }
+        line.push_str(if self.success { " (ok)" } else { " (failed)" });
+        line
+    }
+}
+
+fn clean_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+pub fn audit_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(AUDIT_FILE_NAME)
+}
+
+fn rotated_path(data_dir: &Path, index: usize) -> PathBuf {
+    data_dir.join(format!("{AUDIT_FILE_NAME}.{index}"))
+}
+
+/// Builds a record for `sender` with world and position filled in for players.
+pub fn record_for(
+    sender: &CommandSender,
+    command: &str,
+    targets: Vec<String>,
+    details: String,
+    success: bool,
+) -> AuditRecord {
+    let (actor, world, position) = match sender.as_player() {
+        Some(player) => {
+            let pos = player.position();
+            (
+                player.gameprofile.name.clone(),
+                Some(player.world().dimension.minecraft_name.to_string()),
+                Some(format!("{:.0} {:.0} {:.0}", pos.x, pos.y, pos.z)),
+            )
+        }
+        None => ("Console".to_string(), None, None),
+    };
+    AuditRecord {
+        time: unix_now(),
+        actor,
+        command: command.to_string(),
+        targets,
+        details,
+        world,
+        position,
+        success,
+    }
+}
+
+#[derive(Clone)]
+pub struct AuditLog {
+    inner: Arc<AuditInner>,
+}
+
+struct AuditInner {
+    config: Arc<RwLock<Config>>,
+    data_dir: PathBuf,
+    discord: Option<DiscordBridge>,
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    pub fn new(
+        config: Arc<RwLock<Config>>,
+        data_dir: PathBuf,
+        discord: Option<DiscordBridge>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(AuditInner {
+                config,
+                data_dir,
+                discord,
+                write_lock: Mutex::new(()),
+            }),
+        }
+    }
+
+    /// Wraps an executor so every run of `command` is recorded.
+    pub fn wrap<E: CommandExecutor>(&self, command: &'static str, executor: E) -> Audited<E> {
+        Audited {
+            audit: self.clone(),
+            command,
+            executor,
+        }
+    }
+
+    pub fn append(&self, record: &AuditRecord) {
+        let config = {
+            let guard = self.inner.config.read().unwrap();
+            guard.clone()
+        };
+        if !config.audit_enabled {
+            return;
+        }
+
+        {
+            let _guard = self.inner.write_lock.lock().unwrap();
+            if let Err(err) = self.write_line(&config, &record.to_line()) {
+                log::warn!("Failed to write {AUDIT_FILE_NAME}: {err}");
+            }
+        }
+
+        send_discord_message(
+            self.inner.discord.as_ref(),
+            &config,
+            DiscordRoute::Moderation,
+            format!("[Audit] {}", escape_markdown(&record.summary())),
+        );
+    }
+
+    fn write_line(&self, config: &Config, line: &str) -> Result<(), String> {
+        let data_dir = &self.inner.data_dir;
+        fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+        let path = audit_path(data_dir);
+        let max_bytes = config.audit_max_file_kb.max(1) * 1024;
+        if fs::metadata(&path).is_ok_and(|meta| meta.len() >= max_bytes) {
+            self.rotate(config.audit_max_files)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| e.to_string())?;
+        writeln!(file, "{line}").map_err(|e| e.to_string())
+    }
+
+    fn rotate(&self, max_files: usize) -> Result<(), String> {
+        let data_dir = &self.inner.data_dir;
+        if max_files == 0 {
+            return fs::remove_file(audit_path(data_dir)).map_err(|e| e.to_string());
+        }
+        let oldest = rotated_path(data_dir, max_files);
+        if oldest.exists() {
+            fs::remove_file(&oldest).map_err(|e| e.to_string())?;
+        }
+        for index in (1..max_files).rev() {
+            let from = rotated_path(data_dir, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(data_dir, index + 1)).map_err(|e| e.to_string())?;
+            }
+        }
+        fs::rename(audit_path(data_dir), rotated_path(data_dir, 1)).map_err(|e| e.to_string())
+    }
+
+    /// Returns records newest first, optionally limited to those involving `player`.
+    pub fn query(&self, player: Option<&str>) -> Vec<AuditRecord> {
+        let max_files = self.inner.config.read().unwrap().audit_max_files;
+        let data_dir = &self.inner.data_dir;
+        let paths = std::iter::once(audit_path(data_dir))
+            .chain((1..=max_files).map(|index| rotated_path(data_dir, index)));
+
+        let mut records = Vec::new();
+        for path in paths {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            records.extend(
+                content
+                    .lines()
+                    .rev()
+                    .filter_map(AuditRecord::from_line)
+                    .filter(|record| player.is_none_or(|name| record.involves(name))),
+            );
+        }
+        records
+    }
+}
+
+tokio::task_local! {
+    static FAILED: Cell<bool>;
+}
+
+/// Records the running audited command as failed. Executors report their own errors to the
+/// sender and still return `Ok`, so this is how `Audited` learns about them. Does nothing
+/// outside an audited command.
+pub fn mark_failed() {
+    let _ = FAILED.try_with(|failed| failed.set(true));
+}
+
+pub struct Audited<E> {
+    audit: AuditLog,
+    command: &'static str,
+    executor: E,
+}
+
+impl<E: CommandExecutor> CommandExecutor for Audited<E> {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let (result, failed) = FAILED
+                .scope(Cell::new(false), async {
+                    let result = self.executor.execute(sender, server, args).await;
+                    (result, FAILED.with(Cell::get))
+                })
+                .await;
+
+            let mut keys = args.keys().copied().collect::<Vec<_>>();
+            keys.sort_unstable();
+            let mut targets = Vec::new();
+            let mut details = Vec::new();
+            for key in keys {
+                match &args[key] {
+                    Arg::Players(players) => targets.extend(
+                        players
+                            .iter()
+                            .map(|player| player.gameprofile.name.clone()),
+                    ),
+                    Arg::Simple(value) if TARGET_ARGS.contains(&key) => {
+                        targets.push((*value).to_string());
+                    }
+                    Arg::Simple(value) => details.push(format!("{key}={value}")),
+                    Arg::Msg(value) => details.push(format!("{key}={value}")),
+                    _ => {}
+                }
+            }
+
+            let record = record_for(
+                sender,
+                self.command,
+                targets,
+                details.join(" "),
+                result.is_ok() && !failed,
+            );
+            self.audit.append(&record);
+            result
+        })
+    }
+}
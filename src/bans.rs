@@ -0,0 +1,197 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use pumpkin::plugin::{BoxFuture, Cancellable, EventHandler};
+use pumpkin::plugin::events::player::player_login::PlayerLoginEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::alts::address_key;
+use crate::config::Config;
+use crate::seen::{format_duration, unix_now};
+
+const BANS_FILE_NAME: &str = "bans.yml";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BanKind {
+    Player,
+    Ip,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BanEntry {
+    pub kind: BanKind,
+    pub uuid: Option<Uuid>,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub issuer: String,
+    pub reason: String,
+    pub issued_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+impl BanEntry {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    pub fn expires_label(&self, config: &Config, now: u64) -> String {
+        match self.expires_at {
+            Some(expires_at) => format!(
+                "in {}",
+                format_duration(Duration::from_secs(expires_at.saturating_sub(now)))
+            ),
+            None => config.ban_permanent_label.clone(),
+        }
+    }
+
+    pub fn target_label(&self) -> String {
+        match self.kind {
+            BanKind::Player => self.name.clone().unwrap_or_else(|| "unknown".to_string()),
+            BanKind::Ip => match &self.name {
+                Some(name) => format!(
+                    "{} ({name})",
+                    self.address.clone().unwrap_or_default()
+                ),
+                None => self.address.clone().unwrap_or_default(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BanFile {
+    bans: Vec<BanEntry>,
+}
+
+pub type BanList = Arc<RwLock<Vec<BanEntry>>>;
+
+pub fn bans_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(BANS_FILE_NAME)
+}
+
+pub fn load_bans(path: &Path) -> Result<Vec<BanEntry>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file = serde_yaml::from_str::<BanFile>(&content).map_err(|e| e.to_string())?;
+    Ok(file.bans)
+}
+
+pub fn save_bans(path: &Path, bans: &[BanEntry]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let file = BanFile {
+        bans: bans.to_vec(),
+    };
+    let content = serde_yaml::to_string(&file).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Drops expired bans and persists the list if anything changed.
+pub fn prune_expired(bans: &BanList, data_dir: &Path) {
+    let now = unix_now();
+    let mut guard = bans.write().unwrap();
+    let before = guard.len();
+    guard.retain(|entry| !entry.is_expired(now));
+    if guard.len() != before {
+        if let Err(err) = save_bans(&bans_path(data_dir), &guard) {
+            log::warn!("Failed to save bans.yml: {err}");
+        }
+    }
+}
+
+pub fn find_ban(
+    bans: &BanList,
+    data_dir: &Path,
+    uuid: Uuid,
+    addresses: &[String],
+) -> Option<BanEntry> {
+    prune_expired(bans, data_dir);
+    let guard = bans.read().unwrap();
+    guard
+        .iter()
+        .find(|entry| entry.kind == BanKind::Player && entry.uuid == Some(uuid))
+        .or_else(|| {
+            guard.iter().find(|entry| {
+                entry.kind == BanKind::Ip
+                    && entry
+                        .address
+                        .as_ref()
+                        .is_some_and(|address| addresses.contains(address))
+            })
+        })
+        .cloned()
+}
+
+pub fn ban_screen(config: &Config, entry: &BanEntry) -> TextComponent {
+    let template = match entry.kind {
+        BanKind::Player => &config.ban_screen,
+        BanKind::Ip => &config.ipban_screen,
+    };
+    let text = template
+        .replace("{REASON}", &entry.reason)
+        .replace("{EXPIRES}", &entry.expires_label(config, unix_now()))
+        .replace("{ISSUER}", &entry.issuer);
+    TextComponent::text(text).color_named(NamedColor::Red)
+}
+
+pub struct BanLoginHandler {
+    config: Arc<RwLock<Config>>,
+    bans: BanList,
+    data_dir: PathBuf,
+    address_salt: String,
+}
+
+impl BanLoginHandler {
+    pub fn new(
+        config: Arc<RwLock<Config>>,
+        bans: BanList,
+        data_dir: PathBuf,
+        address_salt: String,
+    ) -> Self {
+        Self {
+            config,
+            bans,
+            data_dir,
+            address_salt,
+        }
+    }
+}
+
+impl EventHandler<PlayerLoginEvent> for BanLoginHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerLoginEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+
+            let ip = event.player.client.address().await.ip().to_string();
+            let addresses = [address_key(&config, &self.address_salt, &ip), ip];
+            let Some(entry) = find_ban(
+                &self.bans,
+                &self.data_dir,
+                event.player.gameprofile.id,
+                &addresses,
+            ) else {
+                return;
+            };
+
+            event.kick_message = ban_screen(&config, &entry);
+            event.set_cancelled(true);
+        })
+    }
+}
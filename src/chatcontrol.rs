@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use pumpkin::plugin::{BoxFuture, Cancellable, EventHandler};
+use pumpkin::plugin::events::player::player_chat::PlayerChatEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use uuid::Uuid;
+
+use crate::config::Config;
+
+pub const PERMISSION_CHAT_BYPASS: &str = "JinxCore:chat.bypass";
+
+/// Global chat restrictions set with `/chat`. Independent of the anti-spam burst rules.
+#[derive(Default)]
+pub struct ChatControl {
+    pub locked: bool,
+    pub slow_secs: u64,
+    last_message: HashMap<Uuid, Instant>,
+}
+
+impl ChatControl {
+    pub fn set_slow(&mut self, secs: u64) {
+        self.slow_secs = secs;
+        self.last_message.clear();
+    }
+
+    pub fn status_label(&self) -> &'static str {
+        if self.locked { "Locked" } else { "Open" }
+    }
+
+    pub fn slow_label(&self) -> String {
+        if self.slow_secs == 0 {
+            "off".to_string()
+        } else {
+            format!("{}s", self.slow_secs)
+        }
+    }
+
+    /// Records a message and returns how long the player still has to wait, if at all.
+    fn check_slow(&mut self, uuid: Uuid, now: Instant) -> Option<Duration> {
+        if self.slow_secs == 0 {
+            return None;
+        }
+        let interval = Duration::from_secs(self.slow_secs);
+        // Entries past the interval no longer limit anyone, so they are dropped to keep the map
+        // at the players who chatted recently.
+        self.last_message
+            .retain(|_, last| now.duration_since(*last) < interval);
+        if let Some(last) = self.last_message.get(&uuid) {
+            let elapsed = now.duration_since(*last);
+            if elapsed < interval {
+                return Some(interval - elapsed);
+            }
+        }
+        self.last_message.insert(uuid, now);
+        None
+    }
+}
+
+pub type ChatControlState = Arc<RwLock<ChatControl>>;
+
+pub struct ChatControlHandler {
+    config: Arc<RwLock<Config>>,
+    control: ChatControlState,
+}
+
+impl ChatControlHandler {
+    pub fn new(config: Arc<RwLock<Config>>, control: ChatControlState) -> Self {
+        Self { config, control }
+    }
+}
+
+impl EventHandler<PlayerChatEvent> for ChatControlHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerChatEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if event.cancelled() {
+                return;
+            }
+            {
+                let control = self.control.read().unwrap();
+                if !control.locked && control.slow_secs == 0 {
+                    return;
+                }
+            }
+            if event.player.has_permission(PERMISSION_CHAT_BYPASS).await {
+                return;
+            }
+
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+
+            let notice = {
+                let mut control = self.control.write().unwrap();
+                if control.locked {
+                    Some(config.chat_locked_message.clone())
+                } else {
+                    control
+                        .check_slow(event.player.gameprofile.id, Instant::now())
+                        .map(|wait| {
+                            config
+                                .chat_slow_message
+                                .replace("{SECONDS}", &wait.as_secs().max(1).to_string())
+                        })
+                }
+            };
+            let Some(notice) = notice else {
+                return;
+            };
+
+            event.set_cancelled(true);
+            let message = TextComponent::text(notice).color_named(NamedColor::Red);
+            event.player.send_system_message(&message).await;
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::argument,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::alts::{address_key, find_alts, known_addresses};
+use crate::commands::moderation_common::resolve_player;
+
+const ARG_NAME: &str = "player";
+
+struct AltsExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for AltsExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("No data for {name}."))
+                        .color_named(NamedColor::Red),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let mut addresses = {
+                let seen = self.state.seen.read().unwrap();
+                known_addresses(&seen, target.uuid)
+            };
+            if let Some(player) = &target.online {
+                let config = self.state.config.read().unwrap().clone();
+                let ip = player.client.address().await.ip().to_string();
+                let current = address_key(&config, &self.state.address_salt, &ip);
+                if !addresses.contains(&current) {
+                    addresses.push(current);
+                }
+            }
+
+            let alts = find_alts(&self.state, target.uuid, &addresses);
+            if alts.is_empty() {
+                let msg = branding::brand(
+                    TextComponent::text(format!(
+                        "No other accounts share an address with {}.",
+                        target.name
+                    ))
+                    .color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let mut lines = format!(
+                "Accounts sharing an address with {} ({}):",
+                target.name,
+                alts.len()
+            );
+            for alt in &alts {
+                let shared = if alt.shared_addresses == 1 {
+                    "1 address".to_string()
+                } else {
+                    format!("{} addresses", alt.shared_addresses)
+                };
+                lines.push_str(&format!("\n- {}{} ({shared})", alt.name, alt.flags()));
+            }
+
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn alts_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["alts"], "List accounts that share an address with a player.")
+        .then(argument(ARG_NAME, SimpleArgConsumer).execute(AltsExecutor { state }))
+}
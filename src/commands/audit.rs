@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs},
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+
+const ARG_PLAYER: &str = "player";
+const ARG_PAGE: &str = "page";
+const PAGE_SIZE: usize = 8;
+
+pub struct AuditExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for AuditExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let player = match args.get(ARG_PLAYER) {
+                Some(Arg::Simple(value)) => Some(*value),
+                _ => None,
+            };
+            let page = match args.get(ARG_PAGE) {
+                Some(Arg::Simple(value)) => value.parse::<usize>().ok(),
+                _ => None,
+            };
+            // `/jinx audit 3` pages through everything rather than looking up a player named "3".
+            let (player, page) = match (player, page) {
+                (Some(value), None) => match value.parse::<usize>() {
+                    Ok(page) => (None, page),
+                    Err(_) => (Some(value), 1),
+                },
+                (player, page) => (player, page.unwrap_or(1)),
+            };
+            let page = page.max(1);
+
+            let records = self.state.audit.query(player);
+            if records.is_empty() {
+                let text = match player {
+                    Some(name) => format!("No audit entries for {name}."),
+                    None => "The audit log is empty.".to_string(),
+                };
+                let msg =
+                    branding::brand(TextComponent::text(text).color_named(NamedColor::Yellow));
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let pages = records.len().div_ceil(PAGE_SIZE);
+            let page = page.min(pages);
+            let title = match player {
+                Some(name) => format!("Audit log for {name}"),
+                None => "Audit log".to_string(),
+            };
+            let mut lines = format!("{title} ({}) - page {page}/{pages}:", records.len());
+            for record in records.iter().skip((page - 1) * PAGE_SIZE).take(PAGE_SIZE) {
+                lines.push_str(&format!("\n{}", record.summary()));
+            }
+
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn jinx_audit_command(state: Arc<PluginState>) -> AuditExecutor {
+    AuditExecutor { state }
+}
@@ -0,0 +1,372 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, message::MsgArgConsumer, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::argument,
+};
+use pumpkin::net::DisconnectReason;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::alts::address_key;
+use crate::audit::mark_failed;
+use crate::bans::{BanEntry, BanKind, ban_screen, bans_path, prune_expired, save_bans};
+use crate::commands::moderation_common::{
+    issuer_name, reason_or_default, resolve_player, split_duration_reason,
+};
+use crate::seen::{parse_duration, unix_now};
+
+const ARG_TARGET: &str = "target";
+const ARG_ARGS: &str = "reason";
+const ARG_PAGE: &str = "page";
+const PAGE_SIZE: usize = 8;
+
+#[derive(Clone, Copy)]
+enum BanMode {
+    Permanent,
+    Temporary,
+    Address,
+}
+
+struct BanExecutor {
+    state: Arc<PluginState>,
+    mode: BanMode,
+}
+
+struct UnbanExecutor {
+    state: Arc<PluginState>,
+}
+
+struct BanListExecutor {
+    state: Arc<PluginState>,
+}
+
+async fn send_error(sender: &CommandSender, text: String) {
+    mark_failed();
+    let msg = branding::brand(TextComponent::text(text).color_named(NamedColor::Red));
+    sender.send_message(msg).await;
+}
+
+impl CommandExecutor for BanExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(target_name)) = args.get(ARG_TARGET) else {
+                return Ok(());
+            };
+            let raw = match args.get(ARG_ARGS) {
+                Some(Arg::Msg(raw)) => Some(raw.as_str()),
+                _ => None,
+            };
+
+            let (duration, reason) = match self.mode {
+                BanMode::Permanent => (None, reason_or_default(raw.unwrap_or_default())),
+                BanMode::Temporary => {
+                    let raw = raw.unwrap_or_default().trim();
+                    let (first, rest) = raw.split_once(' ').unwrap_or((raw, ""));
+                    let Some(duration) = parse_duration(first) else {
+                        let msg = branding::brand(
+                            TextComponent::text(
+                                "Usage: /tempban <player> <duration> [reason] (e.g. 7d12h)",
+                            )
+                            .color_named(NamedColor::Yellow),
+                        );
+                        sender.send_message(msg).await;
+                        return Ok(());
+                    };
+                    (Some(duration), reason_or_default(rest))
+                }
+                BanMode::Address => split_duration_reason(raw),
+            };
+
+            let now = unix_now();
+            let mut entry = BanEntry {
+                kind: BanKind::Player,
+                uuid: None,
+                name: None,
+                address: None,
+                issuer: issuer_name(sender),
+                reason,
+                issued_at: now,
+                expires_at: duration.map(|duration| now.saturating_add(duration.as_secs())),
+            };
+
+            let config = self.state.config.read().unwrap().clone();
+            let salt = &self.state.address_salt;
+            if let BanMode::Address = self.mode {
+                entry.kind = BanKind::Ip;
+                if let Ok(ip) = target_name.parse::<IpAddr>() {
+                    entry.address = Some(address_key(&config, salt, &ip.to_string()));
+                } else if let Some(target) = resolve_player(server, &self.state, target_name).await
+                {
+                    let address = match &target.online {
+                        Some(player) => {
+                            let ip = player.client.address().await.ip().to_string();
+                            Some(address_key(&config, salt, &ip))
+                        }
+                        None => {
+                            let seen = self.state.seen.read().unwrap();
+                            seen.get(&target.uuid)
+                                .and_then(|entry| entry.last_address.clone())
+                        }
+                    };
+                    let Some(address) = address else {
+                        send_error(sender, format!("No known address for {}.", target.name)).await;
+                        return Ok(());
+                    };
+                    entry.uuid = Some(target.uuid);
+                    entry.name = Some(target.name);
+                    entry.address = Some(address);
+                } else {
+                    send_error(sender, format!("No data for {target_name}.")).await;
+                    return Ok(());
+                }
+            } else {
+                let Some(target) = resolve_player(server, &self.state, target_name).await else {
+                    send_error(sender, format!("No data for {target_name}.")).await;
+                    return Ok(());
+                };
+                entry.uuid = Some(target.uuid);
+                entry.name = Some(target.name);
+            }
+
+            let save_result = {
+                let mut bans = self.state.bans.write().unwrap();
+                bans.retain(|existing| {
+                    existing.kind != entry.kind
+                        || match entry.kind {
+                            BanKind::Player => existing.uuid != entry.uuid,
+                            BanKind::Ip => existing.address != entry.address,
+                        }
+                });
+                bans.push(entry.clone());
+                save_bans(&bans_path(&self.state.data_dir), &bans)
+            };
+            if let Err(err) = save_result {
+                send_error(sender, format!("Failed to save bans.yml: {err}")).await;
+                return Ok(());
+            }
+
+            let screen = ban_screen(&config, &entry);
+            let mut kicked = 0usize;
+            for world in server.worlds.read().await.iter() {
+                let players = world.players.read().await.values().cloned().collect::<Vec<_>>();
+                for player in players {
+                    let matches = match entry.kind {
+                        BanKind::Player => entry.uuid == Some(player.gameprofile.id),
+                        BanKind::Ip => {
+                            let ip = player.client.address().await.ip().to_string();
+                            entry.address == Some(address_key(&config, salt, &ip))
+                        }
+                    };
+                    if matches {
+                        player.kick(DisconnectReason::Kicked, screen.clone()).await;
+                        kicked += 1;
+                    }
+                }
+            }
+
+            let mut text = format!(
+                "Banned {} (expires {}). Reason: {}",
+                entry.target_label(),
+                entry.expires_label(&config, now),
+                entry.reason
+            );
+            if kicked > 1 {
+                text.push_str(&format!(" Kicked {kicked} players."));
+            }
+            let msg = branding::brand(TextComponent::text(text).color_named(NamedColor::Green));
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for UnbanExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(target_name)) = args.get(ARG_TARGET) else {
+                return Ok(());
+            };
+
+            let config = self.state.config.read().unwrap().clone();
+            let address = target_name
+                .parse::<IpAddr>()
+                .ok()
+                .map(|ip| ip.to_string());
+            let hashed = address
+                .as_ref()
+                .map(|ip| address_key(&config, &self.state.address_salt, ip));
+            let uuid = if address.is_none() {
+                resolve_player(server, &self.state, target_name)
+                    .await
+                    .map(|target| target.uuid)
+            } else {
+                None
+            };
+
+            let (removed, save_result) = {
+                let mut bans = self.state.bans.write().unwrap();
+                let before = bans.len();
+                bans.retain(|entry| match &address {
+                    Some(address) => {
+                        entry.address.as_ref() != Some(address) && entry.address != hashed
+                    }
+                    None => {
+                        entry.uuid.is_none_or(|id| Some(id) != uuid)
+                            && !entry
+                                .name
+                                .as_deref()
+                                .is_some_and(|name| name.eq_ignore_ascii_case(target_name))
+                    }
+                });
+                let removed = before - bans.len();
+                let save_result = if removed > 0 {
+                    save_bans(&bans_path(&self.state.data_dir), &bans)
+                } else {
+                    Ok(())
+                };
+                (removed, save_result)
+            };
+
+            if let Err(err) = save_result {
+                send_error(sender, format!("Failed to save bans.yml: {err}")).await;
+                return Ok(());
+            }
+
+            let msg = if removed == 0 {
+                branding::brand(
+                    TextComponent::text(format!("{target_name} is not banned."))
+                        .color_named(NamedColor::Yellow),
+                )
+            } else {
+                branding::brand(
+                    TextComponent::text(format!("Unbanned {target_name}."))
+                        .color_named(NamedColor::Green),
+                )
+            };
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for BanListExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let page = match args.get(ARG_PAGE) {
+                Some(Arg::Simple(value)) => value.parse::<usize>().unwrap_or(1).max(1),
+                _ => 1,
+            };
+
+            prune_expired(&self.state.bans, &self.state.data_dir);
+            let mut bans = self.state.bans.read().unwrap().clone();
+            if bans.is_empty() {
+                let msg = branding::brand(
+                    TextComponent::text("No active bans.").color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            bans.sort_by(|a, b| b.issued_at.cmp(&a.issued_at));
+            let pages = bans.len().div_ceil(PAGE_SIZE);
+            let page = page.min(pages);
+            let config = self.state.config.read().unwrap().clone();
+            let now = unix_now();
+
+            let mut lines = format!("Bans ({}) - page {page}/{pages}:", bans.len());
+            for entry in bans.iter().skip((page - 1) * PAGE_SIZE).take(PAGE_SIZE) {
+                let kind = match entry.kind {
+                    BanKind::Player => "ban",
+                    BanKind::Ip => "ipban",
+                };
+                lines.push_str(&format!(
+                    "\n[{kind}] {} - expires {} - by {}: {}",
+                    entry.target_label(),
+                    entry.expires_label(&config, now),
+                    entry.issuer,
+                    entry.reason
+                ));
+            }
+
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+fn ban_tree(
+    names: [&'static str; 1],
+    description: &str,
+    state: Arc<PluginState>,
+    mode: BanMode,
+) -> CommandTree {
+    let audit = &state.audit;
+    CommandTree::new(names, description).then(
+        argument(ARG_TARGET, SimpleArgConsumer)
+            .execute(audit.wrap(
+                names[0],
+                BanExecutor {
+                    state: Arc::clone(&state),
+                    mode,
+                },
+            ))
+            .then(argument(ARG_ARGS, MsgArgConsumer).execute(audit.wrap(
+                names[0],
+                BanExecutor {
+                    state: Arc::clone(&state),
+                    mode,
+                },
+            ))),
+    )
+}
+
+pub fn ban_command_tree(state: Arc<PluginState>) -> CommandTree {
+    ban_tree(["ban"], "Permanently ban a player.", state, BanMode::Permanent)
+}
+
+pub fn tempban_command_tree(state: Arc<PluginState>) -> CommandTree {
+    ban_tree(["tempban"], "Temporarily ban a player.", state, BanMode::Temporary)
+}
+
+pub fn ipban_command_tree(state: Arc<PluginState>) -> CommandTree {
+    ban_tree(["ipban"], "Ban a player's address.", state, BanMode::Address)
+}
+
+pub fn unban_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["unban"], "Remove a ban.")
+        .then(argument(ARG_TARGET, SimpleArgConsumer).execute(state.audit.wrap(
+            "unban",
+            UnbanExecutor {
+                state: Arc::clone(&state),
+            },
+        )))
+}
+
+pub fn banlist_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["banlist"], "List active bans.")
+        .execute(BanListExecutor {
+            state: Arc::clone(&state),
+        })
+        .then(argument(ARG_PAGE, SimpleArgConsumer).execute(BanListExecutor { state }))
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::{argument, literal},
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::audit::mark_failed;
+
+const ARG_SECONDS: &str = "seconds";
+
+#[derive(Clone, Copy)]
+enum ChatAction {
+    Lock,
+    Unlock,
+    Slow,
+}
+
+struct ChatExecutor {
+    state: Arc<PluginState>,
+    action: ChatAction,
+}
+
+impl CommandExecutor for ChatExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let announcement = match self.action {
+                ChatAction::Lock => {
+                    self.state.chat_control.write().unwrap().locked = true;
+                    "Chat has been locked by staff.".to_string()
+                }
+                ChatAction::Unlock => {
+                    self.state.chat_control.write().unwrap().locked = false;
+                    "Chat has been unlocked.".to_string()
+                }
+                ChatAction::Slow => {
+                    let Some(Arg::Simple(raw)) = args.get(ARG_SECONDS) else {
+                        return Ok(());
+                    };
+                    let Ok(secs) = raw.parse::<u64>() else {
+                        let msg = branding::brand(
+                            TextComponent::text("Seconds must be a whole number (0 disables).")
+                                .color_named(NamedColor::Red),
+                        );
+                        mark_failed();
+                        sender.send_message(msg).await;
+                        return Ok(());
+                    };
+                    self.state.chat_control.write().unwrap().set_slow(secs);
+                    if secs == 0 {
+                        "Slow mode has been disabled.".to_string()
+                    } else {
+                        format!("Slow mode enabled: one message every {secs}s.")
+                    }
+                }
+            };
+
+            let message =
+                branding::brand(TextComponent::text(announcement).color_named(NamedColor::Gold));
+            for world in server.worlds.read().await.iter() {
+                for player in world.players.read().await.values() {
+                    player.send_system_message(&message).await;
+                }
+            }
+            if sender.as_player().is_none() {
+                sender.send_message(message).await;
+            }
+            Ok(())
+        })
+    }
+}
+
+pub fn chat_command_tree(state: Arc<PluginState>) -> CommandTree {
+    let executor = |action| ChatExecutor {
+        state: Arc::clone(&state),
+        action,
+    };
+    CommandTree::new(["chat"], "Lock chat or enable slow mode.")
+        .then(literal("lock").execute(state.audit.wrap("chat lock", executor(ChatAction::Lock))))
+        .then(
+            literal("unlock")
+                .execute(state.audit.wrap("chat unlock", executor(ChatAction::Unlock))),
+        )
+        .then(literal("slow").then(
+            argument(ARG_SECONDS, SimpleArgConsumer)
+                .execute(state.audit.wrap("chat slow", executor(ChatAction::Slow))),
+        ))
+}
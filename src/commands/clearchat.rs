@@ -8,6 +8,8 @@ use pumpkin::entity::player::Player;
 use pumpkin::server::Server;
 use pumpkin_util::text::TextComponent;
 
+use crate::audit::AuditLog;
+
 const CLEAR_LINES: usize = 100;
 
 struct ClearChatExecutor;
@@ -47,7 +49,7 @@ async fn collect_players(server: &Server) -> Vec<std::sync::Arc<Player>> {
     players
 }
 
-pub fn clearchat_command_tree() -> CommandTree {
+pub fn clearchat_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["clearchat"], "Clear chat for all players.")
-        .execute(ClearChatExecutor)
+        .execute(audit.wrap("clearchat", ClearChatExecutor))
 }
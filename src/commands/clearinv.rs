@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use pumpkin::command::{
     CommandExecutor, CommandResult, CommandSender,
     args::{Arg, ConsumedArgs, players::PlayersArgumentConsumer},
@@ -9,11 +11,13 @@ use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 use pumpkin_world::item::ItemStack;
 
-use crate::branding;
+use crate::snapshots::{SnapshotReason, snapshot_for_state};
+use crate::{PluginState, branding};
 
 const ARG_TARGET: &str = "target";
 
-async fn clear_player(target: &pumpkin::entity::player::Player) -> u64 {
+async fn clear_player(state: &PluginState, target: &pumpkin::entity::player::Player) -> u64 {
+    snapshot_for_state(target, SnapshotReason::Clearinv, state).await;
     let inventory = target.inventory();
     let mut count: u64 = 0;
     for slot in &inventory.main_inventory {
@@ -35,8 +39,13 @@ async fn clear_player(target: &pumpkin::entity::player::Player) -> u64 {
     count
 }
 
-struct SelfExecutor;
-struct TargetExecutor;
+struct SelfExecutor {
+    state: Arc<PluginState>,
+}
+
+struct TargetExecutor {
+    state: Arc<PluginState>,
+}
 
 impl CommandExecutor for SelfExecutor {
     fn execute<'a>(
@@ -47,7 +56,7 @@ impl CommandExecutor for SelfExecutor {
     ) -> CommandResult<'a> {
         Box::pin(async move {
             let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
-            let count = clear_player(&target).await;
+            let count = clear_player(&self.state, &target).await;
             let msg = branding::brand(
                 TextComponent::text(format!("Cleared {count} items from your inventory."))
                     .color_named(NamedColor::Green),
@@ -75,7 +84,7 @@ impl CommandExecutor for TargetExecutor {
 
             let mut item_count = 0;
             for target in targets {
-                item_count += clear_player(target).await;
+                item_count += clear_player(&self.state, target).await;
                 let msg = branding::brand(
                     TextComponent::text("Your inventory was cleared.")
                         .color_named(NamedColor::Yellow),
@@ -105,8 +114,18 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn clearinv_command_tree() -> CommandTree {
+pub fn clearinv_command_tree(state: Arc<PluginState>) -> CommandTree {
     CommandTree::new(["clearinv"], "Clear inventories.")
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(state.audit.wrap(
+            "clearinv",
+            TargetExecutor {
+                state: Arc::clone(&state),
+            },
+        )))
+        .then(require(|sender| sender.is_player()).execute(state.audit.wrap(
+            "clearinv",
+            SelfExecutor {
+                state: Arc::clone(&state),
+            },
+        )))
 }
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender, args::ConsumedArgs, tree::CommandTree,
+    tree::builder::require,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+
+struct CommandSpyExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for CommandSpyExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(player) = sender.as_player() else {
+                return Ok(());
+            };
+
+            let uuid = player.gameprofile.id;
+            let enabled = {
+                let mut spies = self.state.command_spies.write().unwrap();
+                if spies.remove(&uuid) {
+                    false
+                } else {
+                    spies.insert(uuid);
+                    true
+                }
+            };
+
+            let msg = if enabled {
+                branding::brand(
+                    TextComponent::text("Command spy enabled.").color_named(NamedColor::Green),
+                )
+            } else {
+                branding::brand(
+                    TextComponent::text("Command spy disabled.").color_named(NamedColor::Yellow),
+                )
+            };
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn commandspy_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["commandspy"], "Toggle seeing other players' commands.").then(
+        require(|sender| sender.is_player()).execute(state.audit.wrap(
+            "commandspy",
+            CommandSpyExecutor {
+                state: Arc::clone(&state),
+            },
+        )),
+    )
+}
@@ -7,6 +7,7 @@ use pumpkin::command::{
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_TARGET: &str = "target";
@@ -80,8 +81,11 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn feed_command_tree() -> CommandTree {
+pub fn feed_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["feed"], "Restore a player's hunger.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("feed", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("feed", TargetExecutor)),
+        )
 }
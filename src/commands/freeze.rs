@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::argument,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::audit::mark_failed;
+use crate::commands::moderation_common::issuer_name;
+use crate::freeze::{FrozenEntry, clear_freeze_notice, send_freeze_notice};
+
+const ARG_NAME: &str = "player";
+
+struct FreezeExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for FreezeExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+
+            // Unfreezing works by name so a player who logged out frozen can still be released.
+            let released = {
+                let mut frozen = self.state.frozen.write().unwrap();
+                let uuid = frozen
+                    .iter()
+                    .find(|(_, entry)| entry.name.eq_ignore_ascii_case(name))
+                    .map(|(uuid, _)| *uuid);
+                uuid.and_then(|uuid| frozen.remove(&uuid))
+            };
+            if let Some(entry) = released {
+                if let Some(player) = server.get_player_by_name(&entry.name).await {
+                    let config = self.state.config.read().unwrap().clone();
+                    clear_freeze_notice(&player, &config).await;
+                    let msg = branding::brand(
+                        TextComponent::text("You have been unfrozen.")
+                            .color_named(NamedColor::Green),
+                    );
+                    player.send_system_message(&msg).await;
+                }
+                let msg = branding::brand(
+                    TextComponent::text(format!("Unfroze {}.", entry.name))
+                        .color_named(NamedColor::Green),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let Some(target) = server.get_player_by_name(name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("{name} is not online."))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            self.state.frozen.write().unwrap().insert(
+                target.gameprofile.id,
+                FrozenEntry {
+                    name: target.gameprofile.name.clone(),
+                    issuer: issuer_name(sender),
+                },
+            );
+            let config = self.state.config.read().unwrap().clone();
+            send_freeze_notice(&target, &config).await;
+            let notice = branding::brand(
+                TextComponent::text(config.freeze_message.clone()).color_named(NamedColor::Red),
+            );
+            target.send_system_message(&notice).await;
+
+            let msg = branding::brand(
+                TextComponent::text(format!(
+                    "Froze {}. Run /freeze {} again to release them.",
+                    target.gameprofile.name, target.gameprofile.name
+                ))
+                .color_named(NamedColor::Green),
+            );
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn freeze_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["freeze"], "Freeze or unfreeze a player.").then(
+        argument(ARG_NAME, SimpleArgConsumer).execute(state.audit.wrap(
+            "freeze",
+            FreezeExecutor {
+                state: Arc::clone(&state),
+            },
+        )),
+    )
+}
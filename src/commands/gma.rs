@@ -8,6 +8,7 @@ use pumpkin::server::Server;
 use pumpkin_util::GameMode;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_TARGET: &str = "target";
@@ -96,20 +97,29 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn gma_command_tree() -> CommandTree {
+pub fn gma_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["gma"], "Set Adventure mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("gma", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("gma", TargetExecutor)),
+        )
 }
 
-pub fn adventure_command_tree() -> CommandTree {
+pub fn adventure_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["adventure"], "Set Adventure mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("adventure", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("adventure", TargetExecutor)),
+        )
 }
 
-pub fn a_command_tree() -> CommandTree {
+pub fn a_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["a"], "Set Adventure mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("a", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("a", TargetExecutor)),
+        )
 }
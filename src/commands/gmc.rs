@@ -8,6 +8,7 @@ use pumpkin::server::Server;
 use pumpkin_util::GameMode;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_TARGET: &str = "target";
@@ -96,20 +97,29 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn gmc_command_tree() -> CommandTree {
+pub fn gmc_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["gmc"], "Set Creative mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("gmc", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("gmc", TargetExecutor)),
+        )
 }
 
-pub fn creative_command_tree() -> CommandTree {
+pub fn creative_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["creative"], "Set Creative mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("creative", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("creative", TargetExecutor)),
+        )
 }
 
-pub fn c_command_tree() -> CommandTree {
+pub fn c_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["c"], "Set Creative mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("c", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("c", TargetExecutor)),
+        )
 }
@@ -8,6 +8,7 @@ use pumpkin::server::Server;
 use pumpkin_util::GameMode;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_TARGET: &str = "target";
@@ -96,20 +97,29 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn gms_command_tree() -> CommandTree {
+pub fn gms_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["gms"], "Set Survival mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("gms", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("gms", TargetExecutor)),
+        )
 }
 
-pub fn survival_command_tree() -> CommandTree {
+pub fn survival_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["survival"], "Set Survival mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("survival", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("survival", TargetExecutor)),
+        )
 }
 
-pub fn s_command_tree() -> CommandTree {
+pub fn s_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["s"], "Set Survival mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("s", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("s", TargetExecutor)),
+        )
 }
@@ -8,6 +8,7 @@ use pumpkin::server::Server;
 use pumpkin_util::GameMode;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_TARGET: &str = "target";
@@ -96,20 +97,29 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn gmsp_command_tree() -> CommandTree {
+pub fn gmsp_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["gmsp"], "Set Spectator mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("gmsp", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("gmsp", TargetExecutor)),
+        )
 }
 
-pub fn spectator_command_tree() -> CommandTree {
+pub fn spectator_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["spectator"], "Set Spectator mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("spectator", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("spectator", TargetExecutor)),
+        )
 }
 
-pub fn sp_command_tree() -> CommandTree {
+pub fn sp_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["sp"], "Set Spectator mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("sp", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("sp", TargetExecutor)),
+        )
 }
@@ -9,6 +9,7 @@ use pumpkin::command::{
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_TARGET: &str = "target";
@@ -96,8 +97,11 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn god_command_tree() -> CommandTree {
+pub fn god_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["god"], "Toggle god mode.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("god", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("god", TargetExecutor)),
+        )
 }
@@ -7,6 +7,7 @@ use pumpkin::command::{
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_TARGET: &str = "target";
@@ -78,8 +79,11 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-pub fn heal_command_tree() -> CommandTree {
+pub fn heal_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["heal"], "Heal a player to full health.")
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
-        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(TargetExecutor))
+        .then(require(|sender| sender.is_player()).execute(audit.wrap("heal", SelfExecutor)))
+        .then(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(audit.wrap("heal", TargetExecutor)),
+        )
 }
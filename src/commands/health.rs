@@ -6,12 +6,81 @@ use pumpkin::command::{
 };
 use pumpkin::server::Server;
 use pumpkin_util::text::{color::NamedColor, TextComponent};
+use crate::discord_outbound::QueueStats;
 use crate::{PluginState, branding};
 
 pub struct HealthExecutor {
     pub state: std::sync::Arc<PluginState>,
 }
 
+/// The numbers behind `/jinx health`, shared with the Discord `/health` command.
+pub struct HealthSnapshot {
+    pub tps: f64,
+    pub mspt: f64,
+    pub uptime: String,
+    pub mem_used_kib: u64,
+    pub mem_total_kib: u64,
+    pub disk_used_bytes: u64,
+    pub disk_total_bytes: u64,
+    pub chat_status: &'static str,
+    pub chat_locked: bool,
+    pub slow_label: String,
+    /// `None` when the Discord bot is not running.
+    pub discord_queue: Option<QueueStats>,
+}
+
+impl HealthSnapshot {
+    pub fn collect(server: &Server, state: &PluginState) -> Self {
+        let avg_nanos = server.get_average_tick_time_nanos();
+        let mspt = if avg_nanos <= 0 { 0.0 } else { avg_nanos as f64 / 1_000_000.0 };
+        let tps = if mspt <= 0.0 {
+            0.0
+        } else {
+            let target = server.tick_rate_manager.tickrate() as f64;
+            let current: f64 = 1000.0 / mspt;
+            current.min(target)
+        };
+
+        let metrics = state.system_metrics.read().unwrap().clone();
+        let (chat_status, chat_locked, slow_label) = {
+            let control = state.chat_control.read().unwrap();
+            (control.status_label(), control.locked, control.slow_label())
+        };
+
+        Self {
+            tps,
+            mspt,
+            uptime: format_uptime(state.start_time.elapsed()),
+            mem_used_kib: metrics.mem_used_kib,
+            mem_total_kib: metrics.mem_total_kib,
+            disk_used_bytes: metrics.disk_used_bytes,
+            disk_total_bytes: metrics.disk_total_bytes,
+            chat_status,
+            chat_locked,
+            slow_label,
+            discord_queue: state.discord_bridge.as_ref().map(|bridge| bridge.queue_stats()),
+        }
+    }
+
+    pub fn mem_bar(&self) -> String {
+        let label = format!(
+            "{}/{}",
+            format_gib_from_kib(self.mem_used_kib),
+            format_gib_from_kib(self.mem_total_kib)
+        );
+        usage_bar(self.mem_used_kib as f64, self.mem_total_kib as f64, 20, &label)
+    }
+
+    pub fn disk_bar(&self) -> String {
+        let label = format!(
+            "{}/{}",
+            format_gib_from_bytes(self.disk_used_bytes),
+            format_gib_from_bytes(self.disk_total_bytes)
+        );
+        usage_bar(self.disk_used_bytes as f64, self.disk_total_bytes as f64, 20, &label)
+    }
+}
+
 impl CommandExecutor for HealthExecutor {
     fn execute<'a>(
         &'a self,
@@ -20,48 +89,23 @@ impl CommandExecutor for HealthExecutor {
         _args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
+            let health = HealthSnapshot::collect(server, &self.state);
+            let (tps, mspt) = (health.tps, health.mspt);
 
-            let avg_nanos = server.get_average_tick_time_nanos();
-            let mspt = if avg_nanos <= 0 { 0.0 } else { avg_nanos as f64 / 1_000_000.0 };
-            let tps = if mspt <= 0.0 {
-                0.0
-            } else {
-                let target = server.tick_rate_manager.tickrate() as f64;
-                let current: f64 = 1000.0 / mspt;
-                current.min(target)
-            };
-
-            let uptime = format_uptime(self.state.start_time.elapsed());
-
-            let metrics = self.state.system_metrics.read().unwrap().clone();
-            let total_mem = metrics.mem_total_kib;
-            let used_mem = metrics.mem_used_kib;
-            let mem_label = format!(
-                "{}/{}",
-                format_gib_from_kib(used_mem),
-                format_gib_from_kib(total_mem)
-            );
-            let mem_bar = usage_bar(used_mem as f64, total_mem as f64, 20, &mem_label);
-
-            let total_disk = metrics.disk_total_bytes;
-            let used_disk = metrics.disk_used_bytes;
-            let disk_label = format!(
-                "{}/{}",
-                format_gib_from_bytes(used_disk),
-                format_gib_from_bytes(total_disk)
-            );
-            let disk_bar = usage_bar(used_disk as f64, total_disk as f64, 20, &disk_label);
-
-            let lines = vec![
+            let mut lines = vec![
                 line(
                     "Ram usage",
-                    TextComponent::text(mem_bar)
-                        .color_named(usage_color(used_mem as f64, total_mem as f64)),
+                    TextComponent::text(health.mem_bar()).color_named(usage_color(
+                        health.mem_used_kib as f64,
+                        health.mem_total_kib as f64,
+                    )),
                 ),
                 line(
                     "Storage usage",
-                    TextComponent::text(disk_bar)
-                        .color_named(usage_color(used_disk as f64, total_disk as f64)),
+                    TextComponent::text(health.disk_bar()).color_named(usage_color(
+                        health.disk_used_bytes as f64,
+                        health.disk_total_bytes as f64,
+                    )),
                 ),
                 line(
                     "Average TPS",
@@ -73,9 +117,32 @@ impl CommandExecutor for HealthExecutor {
                 ),
                 line(
                     "Uptime",
-                    TextComponent::text(uptime).color_named(NamedColor::Green),
+                    TextComponent::text(health.uptime.clone()).color_named(NamedColor::Green),
+                ),
+                line(
+                    "Chat",
+                    TextComponent::text(health.chat_status).color_named(if health.chat_locked {
+                        NamedColor::Red
+                    } else {
+                        NamedColor::Green
+                    }),
+                ),
+                line(
+                    "Slow mode",
+                    TextComponent::text(health.slow_label.clone())
+                        .color_named(NamedColor::Yellow),
                 ),
             ];
+            if let Some(queue) = health.discord_queue {
+                lines.push(line(
+                    "Discord queue",
+                    TextComponent::text(queue.label()).color_named(if queue.is_healthy() {
+                        NamedColor::Green
+                    } else {
+                        NamedColor::Red
+                    }),
+                ));
+            }
 
             let body = join_lines(lines);
             sender.send_message(branding::brand(body)).await;
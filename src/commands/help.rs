@@ -8,8 +8,8 @@ use pumpkin_util::text::{color::NamedColor, TextComponent};
 use crate::branding;
 
 const ARG_PAGE: &str = "page";
-const PAGE_COUNT: u32 = 11;
-const COMMAND_COUNT: u32 = 53;
+const PAGE_COUNT: u32 = 17;
+const COMMAND_COUNT: u32 = 84;
 
 pub struct HelpExecutor;
 
@@ -33,7 +33,7 @@ impl CommandExecutor for HelpExecutor {
             .color_named(NamedColor::Gray);
             let body = match page {
                 1 => TextComponent::text(
-                    "Commands (1/11):\n\
+                    "Commands (1/17):\n\
 /tps\n\
 /uptime\n\
 /seen <player>\n\
@@ -42,7 +42,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 2 => TextComponent::text(
-                    "Commands (2/11):\n\
+                    "Commands (2/17):\n\
 /rules\n\
 /discord\n\
 /website\n\
@@ -51,7 +51,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 3 => TextComponent::text(
-                    "Commands (3/11):\n\
+                    "Commands (3/17):\n\
 /jinx reload\n\
 /jinx health\n\
 /jinx credits\n\
@@ -60,7 +60,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 4 => TextComponent::text(
-                    "Commands (4/11):\n\
+                    "Commands (4/17):\n\
 /gmc [player]\n\
 /gms [player]\n\
 /gmsp [player]\n\
@@ -69,7 +69,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 5 => TextComponent::text(
-                    "Commands (5/11):\n\
+                    "Commands (5/17):\n\
 /survival [player]\n\
 /spectator [player]\n\
 /adventure [player]\n\
@@ -78,7 +78,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 6 => TextComponent::text(
-                    "Commands (6/11):\n\
+                    "Commands (6/17):\n\
 /a [player]\n\
 /sp [player]\n\
 /heal [player]\n\
@@ -87,7 +87,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 7 => TextComponent::text(
-                    "Commands (7/11):\n\
+                    "Commands (7/17):\n\
 /god [player]\n\
 /speed <walk|fly> <value> [player]\n\
 /suicide\n\
@@ -96,7 +96,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 8 => TextComponent::text(
-                    "Commands (8/11):\n\
+                    "Commands (8/17):\n\
 /playtime [player]\n\
 /me\n\
 /clearchat\n\
@@ -105,7 +105,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 9 => TextComponent::text(
-                    "Commands (9/11):\n\
+                    "Commands (9/17):\n\
 /day\n\
 /night\n\
 /rain\n\
@@ -114,7 +114,7 @@ impl CommandExecutor for HelpExecutor {
                 )
                 .color_named(NamedColor::White),
                 10 => TextComponent::text(
-                    "Commands (10/11):\n\
+                    "Commands (10/17):\n\
 /calc <expression>\n\
 /online\n\
 /flip\n\
@@ -122,11 +122,66 @@ impl CommandExecutor for HelpExecutor {
 /pl",
                 )
                 .color_named(NamedColor::White),
-                _ => TextComponent::text(
-                    "Commands (11/11):\n\
+                11 => TextComponent::text(
+                    "Commands (11/17):\n\
 /i <targets> <item> [count]\n\
 /starterkit\n\
-/delstarterkit",
+/delstarterkit\n\
+/mute <player> [duration] [reason]\n\
+/tempmute <player> <duration> [reason]",
+                )
+                .color_named(NamedColor::White),
+                12 => TextComponent::text(
+                    "Commands (12/17):\n\
+/unmute <player>\n\
+/mutelist\n\
+/ban <player> [reason]\n\
+/tempban <player> <duration> [reason]\n\
+/ipban <player|ip> [duration] [reason]",
+                )
+                .color_named(NamedColor::White),
+                13 => TextComponent::text(
+                    "Commands (13/17):\n\
+/unban <player|ip>\n\
+/banlist [page]\n\
+/warn <player> <reason>\n\
+/warnings <player>\n\
+/delwarn <id>",
+                )
+                .color_named(NamedColor::White),
+                14 => TextComponent::text(
+                    "Commands (14/17):\n\
+/note add <player> <text>\n\
+/note list <player>\n\
+/note remove <id>\n\
+/alts <player>\n\
+/jinx audit [player] [page]",
+                )
+                .color_named(NamedColor::White),
+                15 => TextComponent::text(
+                    "Commands (15/17):\n\
+/vanish\n\
+/freeze <player>\n\
+/invsee <player>\n\
+/endersee <player>\n\
+/chat lock|unlock|slow <seconds>",
+                )
+                .color_named(NamedColor::White),
+                16 => TextComponent::text(
+                    "Commands (16/17):\n\
+/report <player> <reason>\n\
+/reports\n\
+/reports claim <id>\n\
+/reports close <id> <resolution>\n\
+/commandspy",
+                )
+                .color_named(NamedColor::White),
+                _ => TextComponent::text(
+                    "Commands (17/17):\n\
+/invhistory <player>\n\
+/invrestore <player> [snapshot]\n\
+/link\n\
+/unlink",
                 )
                 .color_named(NamedColor::White),
             };
@@ -6,6 +6,7 @@ use pumpkin::command::{
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_CMD: &str = "cmd";
@@ -37,7 +38,7 @@ impl CommandExecutor for GiveAliasExecutor {
     }
 }
 
-pub fn give_alias_command_tree() -> CommandTree {
+pub fn give_alias_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["i"], "Alias for /give.")
-        .then(argument(ARG_CMD, MsgArgConsumer).execute(GiveAliasExecutor))
+        .then(argument(ARG_CMD, MsgArgConsumer).execute(audit.wrap("i", GiveAliasExecutor)))
 }
@@ -0,0 +1,178 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::argument,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::audit::mark_failed;
+use crate::commands::moderation_common::resolve_player;
+use crate::seen::{format_duration, unix_now};
+use crate::snapshots::{SnapshotReason, load_snapshots, restore_snapshot, snapshot_for_state};
+use crate::{PluginState, branding};
+
+const ARG_NAME: &str = "player";
+const ARG_SNAPSHOT: &str = "snapshot";
+
+struct InvHistoryExecutor {
+    state: Arc<PluginState>,
+}
+
+struct InvRestoreExecutor {
+    state: Arc<PluginState>,
+}
+
+async fn send_error(sender: &CommandSender, text: String) {
+    mark_failed();
+    let msg = branding::brand(TextComponent::text(text).color_named(NamedColor::Red));
+    sender.send_message(msg).await;
+}
+
+impl CommandExecutor for InvHistoryExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                send_error(sender, format!("No data for {name}.")).await;
+                return Ok(());
+            };
+
+            let snapshots = match load_snapshots(&self.state.data_dir, target.uuid) {
+                Ok(snapshots) => snapshots,
+                Err(err) => {
+                    send_error(sender, format!("Failed to read snapshots: {err}")).await;
+                    return Ok(());
+                }
+            };
+            if snapshots.is_empty() {
+                let msg = branding::brand(
+                    TextComponent::text(format!("No inventory snapshots for {}.", target.name))
+                        .color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let now = unix_now();
+            let mut lines = format!("Inventory snapshots for {} (newest first):", target.name);
+            for (index, snapshot) in snapshots.iter().rev().enumerate() {
+                let age = Duration::from_secs(now.saturating_sub(snapshot.taken_at));
+                lines.push_str(&format!(
+                    "\n#{} - {} ago ({}): {} items",
+                    index + 1,
+                    format_duration(age),
+                    snapshot.reason.label(),
+                    snapshot.item_count()
+                ));
+            }
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for InvRestoreExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+            let index = match args.get(ARG_SNAPSHOT) {
+                Some(Arg::Simple(raw)) => match raw.trim_start_matches('#').parse::<usize>() {
+                    Ok(index) if index > 0 => index,
+                    _ => {
+                        send_error(sender, "Usage: /invrestore <player> [snapshot]".to_string())
+                            .await;
+                        return Ok(());
+                    }
+                },
+                _ => 1,
+            };
+
+            let Some(target) = server.get_player_by_name(name).await else {
+                send_error(sender, format!("{name} must be online to restore.")).await;
+                return Ok(());
+            };
+            let snapshots = match load_snapshots(&self.state.data_dir, target.gameprofile.id) {
+                Ok(snapshots) => snapshots,
+                Err(err) => {
+                    send_error(sender, format!("Failed to read snapshots: {err}")).await;
+                    return Ok(());
+                }
+            };
+            // Numbered newest first, matching /invhistory.
+            let Some(snapshot) = snapshots.iter().rev().nth(index - 1).cloned() else {
+                send_error(
+                    sender,
+                    format!("{} has no snapshot #{index}.", target.gameprofile.name),
+                )
+                .await;
+                return Ok(());
+            };
+
+            // The current inventory becomes a snapshot too, so a restore can be undone.
+            snapshot_for_state(&target, SnapshotReason::Restore, &self.state).await;
+            let skipped = restore_snapshot(&target, &snapshot).await;
+
+            let notice = branding::brand(
+                TextComponent::text("Your inventory was restored by staff.")
+                    .color_named(NamedColor::Yellow),
+            );
+            target.send_system_message(&notice).await;
+
+            let text = if skipped > 0 {
+                format!(
+                    "Restored snapshot #{index} for {} ({skipped} stacks skipped).",
+                    target.gameprofile.name
+                )
+            } else {
+                format!("Restored snapshot #{index} for {}.", target.gameprofile.name)
+            };
+            let msg = branding::brand(TextComponent::text(text).color_named(NamedColor::Green));
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn invhistory_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["invhistory"], "List a player's inventory snapshots.").then(
+        argument(ARG_NAME, SimpleArgConsumer).execute(InvHistoryExecutor { state }),
+    )
+}
+
+pub fn invrestore_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["invrestore"], "Restore a player's inventory snapshot.").then(
+        argument(ARG_NAME, SimpleArgConsumer)
+            .execute(state.audit.wrap(
+                "invrestore",
+                InvRestoreExecutor {
+                    state: Arc::clone(&state),
+                },
+            ))
+            .then(argument(ARG_SNAPSHOT, SimpleArgConsumer).execute(state.audit.wrap(
+                "invrestore",
+                InvRestoreExecutor {
+                    state: Arc::clone(&state),
+                },
+            ))),
+    )
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::{argument, require},
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::audit::{mark_failed, record_for};
+use crate::inspect::{InspectKind, InspectScreenFactory, PERMISSION_INVSEE_EDIT};
+use crate::{PluginState, branding};
+
+const ARG_NAME: &str = "player";
+
+struct InspectExecutor {
+    state: Arc<PluginState>,
+    kind: InspectKind,
+}
+
+impl CommandExecutor for InspectExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(viewer) = sender.as_player() else {
+                return Ok(());
+            };
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+            let Some(target) = server.get_player_by_name(name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("{name} is not online."))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let editable = viewer.has_permission(PERMISSION_INVSEE_EDIT).await;
+            let template = record_for(
+                sender,
+                "",
+                vec![target.gameprofile.name.clone()],
+                String::new(),
+                true,
+            );
+            let factory = InspectScreenFactory::new(
+                self.kind,
+                &target,
+                editable,
+                self.state.audit.clone(),
+                template,
+            );
+            viewer.open_handled_screen(&factory).await;
+
+            if !editable {
+                let msg = branding::brand(
+                    TextComponent::text("Read-only view: changes are not applied.")
+                        .color_named(NamedColor::Gray),
+                );
+                sender.send_message(msg).await;
+            }
+            Ok(())
+        })
+    }
+}
+
+fn inspect_tree(
+    name: &'static str,
+    description: &str,
+    state: Arc<PluginState>,
+    kind: InspectKind,
+) -> CommandTree {
+    CommandTree::new([name], description).then(
+        require(|sender| sender.is_player()).then(argument(ARG_NAME, SimpleArgConsumer).execute(
+            state.audit.wrap(
+                name,
+                InspectExecutor {
+                    state: Arc::clone(&state),
+                    kind,
+                },
+            ),
+        )),
+    )
+}
+
+pub fn invsee_command_tree(state: Arc<PluginState>) -> CommandTree {
+    inspect_tree("invsee", "View a player's inventory.", state, InspectKind::Inventory)
+}
+
+pub fn endersee_command_tree(state: Arc<PluginState>) -> CommandTree {
+    inspect_tree("endersee", "View a player's ender chest.", state, InspectKind::EnderChest)
+}
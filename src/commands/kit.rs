@@ -17,6 +17,8 @@ use pumpkin_util::text::{TextComponent, color::NamedColor};
 use pumpkin_world::item::ItemStack;
 use serde::{Deserialize, Serialize};
 
+use crate::audit::mark_failed;
+use crate::snapshots::{SnapshotReason, snapshot_for_state};
 use crate::{PluginState, branding};
 
 const ARG_NAME: &str = "name";
@@ -190,6 +192,7 @@ impl CommandExecutor for CreateKitExecutor {
                     TextComponent::text(format!("Failed to save kits.yml: {err}"))
                         .color_named(NamedColor::Red),
                 );
+                mark_failed();
                 sender.send_message(msg).await;
                 return Ok(());
             }
@@ -228,6 +231,7 @@ impl CommandExecutor for KitExecutor {
                         TextComponent::text(format!("Failed to read kits.yml: {err}"))
                             .color_named(NamedColor::Red),
                     );
+                    mark_failed();
                     sender.send_message(msg).await;
                     return Ok(());
                 }
@@ -270,6 +274,7 @@ impl CommandExecutor for KitExecutor {
                 }
             }
 
+            snapshot_for_state(&player, SnapshotReason::Kit, &self.state).await;
             let inventory = player.inventory();
             let mut applied = 0usize;
             let mut skipped = 0usize;
@@ -329,7 +334,12 @@ pub fn createkit_command_tree(state: Arc<PluginState>) -> CommandTree {
             require(|sender| sender.is_player())
                 .then(
                     argument(ARG_NAME, pumpkin::command::args::simple::SimpleArgConsumer)
-                        .then(argument(ARG_DELAY, delay_consumer()).execute(CreateKitExecutor { state })),
+                        .then(argument(ARG_DELAY, delay_consumer()).execute(state.audit.wrap(
+                            "createkit",
+                            CreateKitExecutor {
+                                state: Arc::clone(&state),
+                            },
+                        ))),
                 ),
         )
 }
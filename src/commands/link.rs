@@ -0,0 +1,134 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender, args::ConsumedArgs, tree::CommandTree,
+    tree::builder::require,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::discord_bot::send_member_sync;
+use crate::links::{links_path, save_links, unlink_sync};
+use crate::{PluginState, branding};
+
+struct LinkExecutor {
+    state: Arc<PluginState>,
+}
+
+struct UnlinkExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for LinkExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(player) = sender.as_player() else {
+                return Ok(());
+            };
+            if self.state.discord_bridge.is_none() {
+                let msg = branding::brand(
+                    TextComponent::text("The Discord bot is not enabled.")
+                        .color_named(NamedColor::Red),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let expiry_secs = self.state.config.read().unwrap().link_code_expiry_seconds;
+            let uuid = player.gameprofile.id;
+            let code = {
+                let mut links = self.state.links.write().unwrap();
+                if links.by_uuid(uuid).is_some() {
+                    None
+                } else {
+                    Some(links.create_code(
+                        uuid,
+                        player.gameprofile.name.clone(),
+                        Duration::from_secs(expiry_secs),
+                    ))
+                }
+            };
+
+            let msg = match code {
+                Some(code) => branding::brand(
+                    TextComponent::text(format!(
+                        "Your link code is {code}. Run /link {code} in Discord within {} minutes.",
+                        expiry_secs.div_ceil(60)
+                    ))
+                    .color_named(NamedColor::Green),
+                ),
+                None => branding::brand(
+                    TextComponent::text("Your account is already linked. Use /unlink first.")
+                        .color_named(NamedColor::Yellow),
+                ),
+            };
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for UnlinkExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(player) = sender.as_player() else {
+                return Ok(());
+            };
+
+            let removed = {
+                let mut links = self.state.links.write().unwrap();
+                let removed = links.unlink(player.gameprofile.id);
+                let saved = if removed.is_some() {
+                    save_links(&links_path(&self.state.data_dir), &links)
+                } else {
+                    Ok(())
+                };
+                if let Err(err) = saved {
+                    log::warn!("Failed to save links.yml: {err}");
+                }
+                removed
+            };
+
+            let msg = match removed {
+                Some(entry) => {
+                    let config = self.state.config.read().unwrap().clone();
+                    send_member_sync(
+                        self.state.discord_bridge.as_ref(),
+                        unlink_sync(&config, &entry),
+                    );
+                    branding::brand(
+                        TextComponent::text("Your Discord account was unlinked.")
+                            .color_named(NamedColor::Green),
+                    )
+                }
+                None => branding::brand(
+                    TextComponent::text("Your account is not linked.")
+                        .color_named(NamedColor::Yellow),
+                ),
+            };
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn link_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["link"], "Get a code to link your Discord account.")
+        .then(require(|sender| sender.is_player()).execute(LinkExecutor { state }))
+}
+
+pub fn unlink_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["unlink"], "Unlink your Discord account.")
+        .then(require(|sender| sender.is_player()).execute(UnlinkExecutor { state }))
+}
@@ -38,6 +38,21 @@ mod flip;
 mod pl;
 mod i;
 mod starterkit;
+mod moderation_common;
+mod mute;
+mod ban;
+mod warn;
+mod note;
+mod alts;
+mod audit;
+mod vanish;
+mod freeze;
+mod invsee;
+mod chat;
+mod report;
+mod commandspy;
+mod invrestore;
+mod link;
 
 pub use reload::jinx_command_tree;
 pub use credits::jinx_credits_command;
@@ -52,7 +67,7 @@ pub use god::god_command_tree;
 pub use heal::heal_command_tree;
 pub use help::jinx_help_command;
 pub use seen::seen_command_tree;
-pub use tps::tps_command_tree;
+pub use tps::{tps_command_tree, tps_summary};
 pub use uptime::uptime_command_tree;
 pub use whois::whois_command_tree;
 pub use rules::rules_command_tree;
@@ -60,6 +75,7 @@ pub use discord::discord_command_tree;
 pub use website::website_command_tree;
 pub use store::store_command_tree;
 pub use socials::socials_command_tree;
+pub use socials_common::{load_socials, socials_path};
 pub use gmc::creative_command_tree;
 pub use gms::survival_command_tree;
 pub use gmsp::spectator_command_tree;
@@ -73,9 +89,10 @@ pub use near::near_command_tree;
 pub use kit::{createkit_command_tree, kit_command_tree};
 pub use suicide::suicide_command_tree;
 pub use ping::ping_command_tree;
-pub use health::jinx_health_command;
+pub use health::{HealthSnapshot, jinx_health_command};
+pub use audit::jinx_audit_command;
 pub use coords::coords_command_tree;
-pub use playtime::playtime_command_tree;
+pub use playtime::{current_playtime_secs, playtime_command_tree};
 pub use clearchat::clearchat_command_tree;
 pub use me::me_command_tree;
 pub use time::{day_command_tree, night_command_tree};
@@ -86,3 +103,21 @@ pub use flip::flip_command_tree;
 pub use pl::plugins_alias_command_tree;
 pub use i::give_alias_command_tree;
 pub use starterkit::{starterkit_command_tree, delstarterkit_command_tree, apply_starterkit};
+pub use mute::{
+    mute_command_tree, mutelist_command_tree, tempmute_command_tree, unmute_command_tree,
+};
+pub use ban::{
+    ban_command_tree, banlist_command_tree, ipban_command_tree, tempban_command_tree,
+    unban_command_tree,
+};
+pub use warn::{delwarn_command_tree, warn_command_tree, warnings_command_tree};
+pub use note::note_command_tree;
+pub use alts::alts_command_tree;
+pub use vanish::vanish_command_tree;
+pub use freeze::freeze_command_tree;
+pub use invsee::{endersee_command_tree, invsee_command_tree};
+pub use chat::chat_command_tree;
+pub use report::{report_command_tree, reports_command_tree};
+pub use commandspy::commandspy_command_tree;
+pub use invrestore::{invhistory_command_tree, invrestore_command_tree};
+pub use link::{link_command_tree, unlink_command_tree};
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use pumpkin::command::CommandSender;
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use uuid::Uuid;
+
+use crate::PluginState;
+use crate::seen::{find_by_name, parse_duration};
+
+pub const DEFAULT_REASON: &str = "No reason given.";
+
+pub struct ResolvedPlayer {
+    pub uuid: Uuid,
+    pub name: String,
+    pub online: Option<Arc<Player>>,
+}
+
+/// Looks a player up by name, falling back to the seen database for offline players.
+pub async fn resolve_player(
+    server: &Server,
+    state: &PluginState,
+    name: &str,
+) -> Option<ResolvedPlayer> {
+    if let Some(player) = server.get_player_by_name(name).await {
+        return Some(ResolvedPlayer {
+            uuid: player.gameprofile.id,
+            name: player.gameprofile.name.clone(),
+            online: Some(player),
+        });
+    }
+
+    let seen = state.seen.read().unwrap();
+    find_by_name(&seen, name).map(|entry| ResolvedPlayer {
+        uuid: entry.uuid,
+        name: entry.name.clone(),
+        online: None,
+    })
+}
+
+/// Splits `[duration] [reason]` where the duration is optional.
+pub fn split_duration_reason(raw: Option<&str>) -> (Option<Duration>, String) {
+    let raw = raw.unwrap_or_default().trim();
+    let (first, rest) = raw.split_once(' ').unwrap_or((raw, ""));
+    if let Some(duration) = parse_duration(first) {
+        return (Some(duration), reason_or_default(rest));
+    }
+    (None, reason_or_default(raw))
+}
+
+pub fn reason_or_default(raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        DEFAULT_REASON.to_string()
+    } else {
+        raw.to_string()
+    }
+}
+
+pub fn issuer_name(sender: &CommandSender) -> String {
+    match sender.as_player() {
+        Some(player) => player.gameprofile.name.clone(),
+        None => "Console".to_string(),
+    }
+}
+
+pub async fn sender_has_permission(sender: &CommandSender, node: &str) -> bool {
+    match sender.as_player() {
+        Some(player) => player.has_permission(node).await,
+        None => true,
+    }
+}
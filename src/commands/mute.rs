@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, message::MsgArgConsumer, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::argument,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::audit::mark_failed;
+use crate::commands::moderation_common::{issuer_name, resolve_player, split_duration_reason};
+use crate::mutes::{MuteEntry, active_mute, mute_notice, mutes_path, save_mutes};
+use crate::seen::unix_now;
+
+const ARG_NAME: &str = "player";
+const ARG_ARGS: &str = "duration_reason";
+
+struct MuteExecutor {
+    state: Arc<PluginState>,
+    /// `/tempmute` requires a duration instead of treating a missing one as permanent.
+    temporary: bool,
+}
+
+struct UnmuteExecutor {
+    state: Arc<PluginState>,
+}
+
+struct MuteListExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for MuteExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+            let raw = match args.get(ARG_ARGS) {
+                Some(Arg::Msg(raw)) => Some(raw.as_str()),
+                _ => None,
+            };
+
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("No data for {name}."))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let (duration, reason) = split_duration_reason(raw);
+            if self.temporary && duration.is_none() {
+                let msg = branding::brand(
+                    TextComponent::text(
+                        "Usage: /tempmute <player> <duration> [reason] (e.g. 1h30m)",
+                    )
+                    .color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+            let now = unix_now();
+            let entry = MuteEntry {
+                uuid: target.uuid,
+                name: target.name.clone(),
+                issuer: issuer_name(sender),
+                reason,
+                issued_at: now,
+                expires_at: duration.map(|duration| now.saturating_add(duration.as_secs())),
+            };
+
+            let save_result = {
+                let mut mutes = self.state.mutes.write().unwrap();
+                mutes.insert(target.uuid, entry.clone());
+                save_mutes(&mutes_path(&self.state.data_dir), &mutes)
+            };
+            if let Err(err) = save_result {
+                let msg = branding::brand(
+                    TextComponent::text(format!("Failed to save mutes.yml: {err}"))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            if let Some(player) = &target.online {
+                let config = self.state.config.read().unwrap().clone();
+                player
+                    .send_system_message(&branding::brand(mute_notice(&config, &entry)))
+                    .await;
+            }
+
+            let msg = branding::brand(
+                TextComponent::text(format!(
+                    "Muted {} {}. Reason: {}",
+                    target.name,
+                    entry.remaining_label(now),
+                    entry.reason
+                ))
+                .color_named(NamedColor::Green),
+            );
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for UnmuteExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+
+            let uuid = match resolve_player(server, &self.state, name).await {
+                Some(target) => Some(target.uuid),
+                None => {
+                    let mutes = self.state.mutes.read().unwrap();
+                    mutes
+                        .values()
+                        .find(|entry| entry.name.eq_ignore_ascii_case(name))
+                        .map(|entry| entry.uuid)
+                }
+            };
+
+            let removed = uuid.and_then(|uuid| {
+                let mut mutes = self.state.mutes.write().unwrap();
+                let removed = mutes.remove(&uuid)?;
+                if let Err(err) = save_mutes(&mutes_path(&self.state.data_dir), &mutes) {
+                    log::warn!("Failed to save mutes.yml: {err}");
+                }
+                Some(removed)
+            });
+
+            let Some(removed) = removed else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("{name} is not muted."))
+                        .color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            if let Some(player) = server.get_player_by_name(&removed.name).await {
+                let msg = branding::brand(
+                    TextComponent::text("You are no longer muted.")
+                        .color_named(NamedColor::Green),
+                );
+                player.send_system_message(&msg).await;
+            }
+
+            let msg = branding::brand(
+                TextComponent::text(format!("Unmuted {}.", removed.name))
+                    .color_named(NamedColor::Green),
+            );
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for MuteListExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let uuids = {
+                let mutes = self.state.mutes.read().unwrap();
+                mutes.keys().copied().collect::<Vec<_>>()
+            };
+            let mut entries = uuids
+                .into_iter()
+                .filter_map(|uuid| active_mute(&self.state.mutes, &self.state.data_dir, uuid))
+                .collect::<Vec<_>>();
+
+            if entries.is_empty() {
+                let msg = branding::brand(
+                    TextComponent::text("No players are muted.").color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+            let now = unix_now();
+            let mut lines = format!("Muted players ({}):", entries.len());
+            for entry in &entries {
+                lines.push_str(&format!(
+                    "\n{} - {} by {}: {}",
+                    entry.name,
+                    entry.remaining_label(now),
+                    entry.issuer,
+                    entry.reason
+                ));
+            }
+
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+fn mute_tree(
+    names: [&'static str; 1],
+    description: &str,
+    state: Arc<PluginState>,
+    temporary: bool,
+) -> CommandTree {
+    let audit = &state.audit;
+    CommandTree::new(names, description).then(
+        argument(ARG_NAME, SimpleArgConsumer)
+            .execute(audit.wrap(
+                names[0],
+                MuteExecutor {
+                    state: Arc::clone(&state),
+                    temporary,
+                },
+            ))
+            .then(argument(ARG_ARGS, MsgArgConsumer).execute(audit.wrap(
+                names[0],
+                MuteExecutor {
+                    state: Arc::clone(&state),
+                    temporary,
+                },
+            ))),
+    )
+}
+
+pub fn mute_command_tree(state: Arc<PluginState>) -> CommandTree {
+    mute_tree(["mute"], "Mute a player.", state, false)
+}
+
+pub fn tempmute_command_tree(state: Arc<PluginState>) -> CommandTree {
+    mute_tree(["tempmute"], "Temporarily mute a player.", state, true)
+}
+
+pub fn unmute_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["unmute"], "Unmute a player.")
+        .then(argument(ARG_NAME, SimpleArgConsumer).execute(state.audit.wrap(
+            "unmute",
+            UnmuteExecutor {
+                state: Arc::clone(&state),
+            },
+        )))
+}
+
+pub fn mutelist_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["mutelist"], "List muted players.").execute(MuteListExecutor { state })
+}
@@ -9,12 +9,15 @@ use pumpkin::entity::player::Player;
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
-use crate::branding;
+use crate::{PluginState, branding};
+use crate::vanish::filter_visible;
 
 const NEAR_RADIUS: f64 = 200.0;
 const MAX_LIST: usize = 10;
 
-struct NearExecutor;
+struct NearExecutor {
+    state: Arc<PluginState>,
+}
 
 impl CommandExecutor for NearExecutor {
     fn execute<'a>(
@@ -32,6 +35,7 @@ impl CommandExecutor for NearExecutor {
 
             let mut candidates = collect_players(server).await;
             candidates.retain(|p| Arc::ptr_eq(&p.living_entity.entity.world, world));
+            filter_visible(sender, &self.state.vanished, &mut candidates).await;
 
             let mut nearby = Vec::new();
             for other in candidates {
@@ -86,7 +90,7 @@ async fn collect_players(server: &Server) -> Vec<Arc<Player>> {
     players
 }
 
-pub fn near_command_tree() -> CommandTree {
+pub fn near_command_tree(state: Arc<PluginState>) -> CommandTree {
     CommandTree::new(["near"], "List nearby players.")
-        .execute(NearExecutor)
+        .execute(NearExecutor { state })
 }
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, message::MsgArgConsumer, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::{argument, literal},
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::audit::mark_failed;
+use crate::commands::moderation_common::{issuer_name, resolve_player};
+use crate::notes::{notes_path, save_notes};
+use crate::seen::format_date;
+
+const ARG_NAME: &str = "player";
+const ARG_TEXT: &str = "text";
+const ARG_ID: &str = "id";
+
+struct NoteAddExecutor {
+    state: Arc<PluginState>,
+}
+
+struct NoteListExecutor {
+    state: Arc<PluginState>,
+}
+
+struct NoteRemoveExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for NoteAddExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+            let Some(Arg::Msg(text)) = args.get(ARG_TEXT) else {
+                return Ok(());
+            };
+
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("No data for {name}."))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let (entry, save_result) = {
+                let mut store = self.state.notes.write().unwrap();
+                let entry = store.add(
+                    target.uuid,
+                    target.name.clone(),
+                    issuer_name(sender),
+                    text.trim().to_string(),
+                );
+                (entry, save_notes(&notes_path(&self.state.data_dir), &store))
+            };
+            if let Err(err) = save_result {
+                let msg = branding::brand(
+                    TextComponent::text(format!("Failed to save notes.yml: {err}"))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let msg = branding::brand(
+                TextComponent::text(format!("Added note #{} to {}.", entry.id, target.name))
+                    .color_named(NamedColor::Green),
+            );
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for NoteListExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("No data for {name}."))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let notes = self.state.notes.read().unwrap().for_player(target.uuid);
+            if notes.is_empty() {
+                let msg = branding::brand(
+                    TextComponent::text(format!("{} has no notes.", target.name))
+                        .color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let mut lines = format!("Notes for {} ({}):", target.name, notes.len());
+            for entry in &notes {
+                lines.push_str(&format!(
+                    "\n#{} {} {}: {}",
+                    entry.id,
+                    format_date(entry.created_at),
+                    entry.author,
+                    entry.text
+                ));
+            }
+
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for NoteRemoveExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(raw_id)) = args.get(ARG_ID) else {
+                return Ok(());
+            };
+            let Ok(id) = raw_id.trim_start_matches('#').parse::<u64>() else {
+                let msg = branding::brand(
+                    TextComponent::text("Usage: /note remove <id>")
+                        .color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let (removed, save_result) = {
+                let mut store = self.state.notes.write().unwrap();
+                match store.remove(id) {
+                    Some(removed) => {
+                        let save_result = save_notes(&notes_path(&self.state.data_dir), &store);
+                        (Some(removed), save_result)
+                    }
+                    None => (None, Ok(())),
+                }
+            };
+
+            if let Err(err) = save_result {
+                let msg = branding::brand(
+                    TextComponent::text(format!("Failed to save notes.yml: {err}"))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let msg = match removed {
+                Some(entry) => branding::brand(
+                    TextComponent::text(format!("Removed note #{id} from {}.", entry.name))
+                        .color_named(NamedColor::Green),
+                ),
+                None => branding::brand(
+                    TextComponent::text(format!("No note with id #{id}."))
+                        .color_named(NamedColor::Yellow),
+                ),
+            };
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn note_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["note"], "Manage staff notes.")
+        .then(
+            literal("add").then(
+                argument(ARG_NAME, SimpleArgConsumer).then(
+                    argument(ARG_TEXT, MsgArgConsumer).execute(state.audit.wrap(
+                        "note add",
+                        NoteAddExecutor {
+                            state: Arc::clone(&state),
+                        },
+                    )),
+                ),
+            ),
+        )
+        .then(
+            literal("list").then(argument(ARG_NAME, SimpleArgConsumer).execute(
+                NoteListExecutor {
+                    state: Arc::clone(&state),
+                },
+            )),
+        )
+        .then(
+            literal("remove")
+                .then(argument(ARG_ID, SimpleArgConsumer).execute(state.audit.wrap(
+                    "note remove",
+                    NoteRemoveExecutor {
+                        state: Arc::clone(&state),
+                    },
+                ))),
+        )
+}
@@ -9,9 +9,12 @@ use pumpkin::entity::player::Player;
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
-use crate::branding;
+use crate::{PluginState, branding};
+use crate::vanish::filter_visible;
 
-struct OnlineExecutor;
+struct OnlineExecutor {
+    state: Arc<PluginState>,
+}
 
 impl CommandExecutor for OnlineExecutor {
     fn execute<'a>(
@@ -21,7 +24,8 @@ impl CommandExecutor for OnlineExecutor {
         _args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
-            let players = collect_players(server).await;
+            let mut players = collect_players(server).await;
+            filter_visible(sender, &self.state.vanished, &mut players).await;
             let mut names: Vec<String> = players
                 .into_iter()
                 .map(|player| player.gameprofile.name.clone())
@@ -66,7 +70,7 @@ async fn collect_players(server: &Server) -> Vec<Arc<Player>> {
     players
 }
 
-pub fn online_command_tree() -> CommandTree {
+pub fn online_command_tree(state: Arc<PluginState>) -> CommandTree {
     CommandTree::new(["online"], "List online players.")
-        .execute(OnlineExecutor)
+        .execute(OnlineExecutor { state })
 }
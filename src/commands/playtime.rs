@@ -78,7 +78,7 @@ impl CommandExecutor for TargetExecutor {
     }
 }
 
-fn current_playtime_secs(state: &PluginState, uuid: uuid::Uuid) -> u64 {
+pub fn current_playtime_secs(state: &PluginState, uuid: uuid::Uuid) -> u64 {
     let base = {
         let totals = state.playtime_total_secs.read().unwrap();
         totals.get(&uuid).copied().unwrap_or(0)
@@ -8,8 +8,11 @@ use pumpkin::server::Server;
 use pumpkin_util::text::TextComponent;
 use pumpkin::command::args::simple::SimpleArgConsumer;
 
+use crate::advertising::Allowlist;
 use crate::{PluginState, config, branding};
-use crate::commands::{jinx_help_command, jinx_credits_command, jinx_health_command};
+use crate::commands::{
+    jinx_audit_command, jinx_credits_command, jinx_health_command, jinx_help_command,
+};
 
 struct ReloadExecutor {
     state: Arc<PluginState>,
@@ -25,6 +28,10 @@ impl CommandExecutor for ReloadExecutor {
         Box::pin(async move {
             match config::load_or_create(&self.state.data_dir) {
                 Ok(new_config) => {
+                    *self.state.advert_allowlist.write().unwrap() = Allowlist::load(
+                        &self.state.data_dir,
+                        &new_config.advert_allowed_domains,
+                    );
                     *self.state.config.write().unwrap() = new_config;
                 }
                 Err(err) => {
@@ -51,5 +58,22 @@ pub fn jinx_command_tree(state: Arc<PluginState>) -> CommandTree {
         )
         .then(literal("credits").execute(jinx_credits_command()))
         .then(literal("health").execute(jinx_health_command(Arc::clone(&state))))
-        .then(literal("reload").execute(ReloadExecutor { state }))
+        .then(
+            literal("audit")
+                .execute(jinx_audit_command(Arc::clone(&state)))
+                .then(
+                    argument("player", SimpleArgConsumer)
+                        .execute(jinx_audit_command(Arc::clone(&state)))
+                        .then(
+                            argument("page", SimpleArgConsumer)
+                                .execute(jinx_audit_command(Arc::clone(&state))),
+                        ),
+                ),
+        )
+        .then(literal("reload").execute(state.audit.wrap(
+            "jinx reload",
+            ReloadExecutor {
+                state: Arc::clone(&state),
+            },
+        )))
 }
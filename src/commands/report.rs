@@ -0,0 +1,362 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, message::MsgArgConsumer, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::{argument, literal, require},
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::audit::mark_failed;
+use crate::commands::moderation_common::{issuer_name, resolve_player, sender_has_permission};
+use crate::reports::{
+    PERMISSION_REPORTS, ReportEntry, ReportStatus, announce_report, reports_path, save_reports,
+};
+use crate::seen::{format_duration, unix_now};
+use crate::staff::notify_staff;
+
+const ARG_NAME: &str = "player";
+const ARG_REASON: &str = "reason";
+const ARG_ID: &str = "id";
+const ARG_RESOLUTION: &str = "resolution";
+
+struct ReportExecutor {
+    state: Arc<PluginState>,
+}
+
+struct ReportsExecutor {
+    state: Arc<PluginState>,
+}
+
+struct ReportClaimExecutor {
+    state: Arc<PluginState>,
+}
+
+struct ReportCloseExecutor {
+    state: Arc<PluginState>,
+}
+
+async fn send_error(sender: &CommandSender, text: String) {
+    mark_failed();
+    let msg = branding::brand(TextComponent::text(text).color_named(NamedColor::Red));
+    sender.send_message(msg).await;
+}
+
+fn parse_id(raw: &str) -> Option<u64> {
+    raw.trim_start_matches('#').parse::<u64>().ok()
+}
+
+impl CommandExecutor for ReportExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(reporter) = sender.as_player() else {
+                return Ok(());
+            };
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+            let Some(Arg::Msg(reason)) = args.get(ARG_REASON) else {
+                return Ok(());
+            };
+
+            let config = self.state.config.read().unwrap().clone();
+            let is_staff = reporter.has_permission(PERMISSION_REPORTS).await;
+            if !is_staff {
+                let last = self
+                    .state
+                    .report_cooldowns
+                    .read()
+                    .unwrap()
+                    .get(&reporter.gameprofile.id)
+                    .copied();
+                let cooldown = Duration::from_secs(config.report_cooldown_seconds);
+                if let Some(last) = last {
+                    let elapsed = last.elapsed();
+                    if elapsed < cooldown {
+                        let wait = format_duration(cooldown - elapsed);
+                        send_error(sender, format!("You can report again in {wait}.")).await;
+                        return Ok(());
+                    }
+                }
+            }
+
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                send_error(sender, format!("No data for {name}.")).await;
+                return Ok(());
+            };
+            if target.uuid == reporter.gameprofile.id {
+                send_error(sender, "You cannot report yourself.".to_string()).await;
+                return Ok(());
+            }
+
+            let (entry, save_result) = {
+                let mut store = self.state.reports.write().unwrap();
+                let entry = store.add(
+                    &reporter,
+                    target.name.clone(),
+                    target.online.as_deref(),
+                    reason.trim().to_string(),
+                );
+                let save_result = save_reports(&reports_path(&self.state.data_dir), &store);
+                (entry, save_result)
+            };
+            if let Err(err) = save_result {
+                log::warn!("Failed to save reports.yml: {err}");
+            }
+            self.state
+                .report_cooldowns
+                .write()
+                .unwrap()
+                .insert(reporter.gameprofile.id, Instant::now());
+
+            let msg = branding::brand(
+                TextComponent::text(format!(
+                    "Thanks, your report #{} against {} has been sent to staff.",
+                    entry.id, entry.target
+                ))
+                .color_named(NamedColor::Green),
+            );
+            sender.send_message(msg).await;
+
+            announce_report(server, &config, self.state.discord_bridge.as_ref(), &entry).await;
+            Ok(())
+        })
+    }
+}
+
+fn describe(entry: &ReportEntry, now: u64) -> String {
+    let age = Duration::from_secs(now.saturating_sub(entry.created_at));
+    let status = match (&entry.status, &entry.claimed_by) {
+        (ReportStatus::Claimed, Some(staff)) => format!(" [claimed by {staff}]"),
+        _ => String::new(),
+    };
+    let target_position = entry.target_position.as_deref().unwrap_or("offline");
+    format!(
+        "\n#{} - {} ago{status}: {} ({}) reported {} ({target_position}): {}",
+        entry.id,
+        format_duration(age),
+        entry.reporter,
+        entry.reporter_position,
+        entry.target,
+        entry.reason
+    )
+}
+
+impl CommandExecutor for ReportsExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let pending = self.state.reports.read().unwrap().pending();
+            if pending.is_empty() {
+                let msg = branding::brand(
+                    TextComponent::text("No open reports.").color_named(NamedColor::Green),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let now = unix_now();
+            let mut lines = format!("Open reports ({}):", pending.len());
+            for entry in &pending {
+                lines.push_str(&describe(entry, now));
+            }
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for ReportClaimExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            if !sender_has_permission(sender, PERMISSION_REPORTS).await {
+                send_error(sender, "You cannot manage reports.".to_string()).await;
+                return Ok(());
+            }
+            let Some(Arg::Simple(raw_id)) = args.get(ARG_ID) else {
+                return Ok(());
+            };
+            let Some(id) = parse_id(raw_id) else {
+                send_error(sender, "Usage: /reports claim <id>".to_string()).await;
+                return Ok(());
+            };
+
+            let staff = issuer_name(sender);
+            let mut save_result = Ok(());
+            let result = {
+                let mut store = self.state.reports.write().unwrap();
+                let result = match store.get_mut(id) {
+                    None => Err(format!("No report with id #{id}.")),
+                    Some(entry) if entry.status == ReportStatus::Closed => {
+                        Err(format!("Report #{id} is already closed."))
+                    }
+                    Some(entry) => {
+                        entry.status = ReportStatus::Claimed;
+                        entry.claimed_by = Some(staff.clone());
+                        Ok(entry.target.clone())
+                    }
+                };
+                if result.is_ok() {
+                    save_result = save_reports(&reports_path(&self.state.data_dir), &store);
+                }
+                result
+            };
+            if let Err(err) = save_result {
+                log::warn!("Failed to save reports.yml: {err}");
+            }
+
+            match result {
+                Ok(target) => {
+                    let msg = branding::brand(
+                        TextComponent::text(format!("{staff} claimed report #{id} ({target})."))
+                            .color_named(NamedColor::Gold),
+                    );
+                    notify_staff(server, PERMISSION_REPORTS, &msg).await;
+                    if sender.as_player().is_none() {
+                        sender.send_message(msg).await;
+                    }
+                }
+                Err(text) => send_error(sender, text).await,
+            }
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for ReportCloseExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            if !sender_has_permission(sender, PERMISSION_REPORTS).await {
+                send_error(sender, "You cannot manage reports.".to_string()).await;
+                return Ok(());
+            }
+            let Some(Arg::Simple(raw_id)) = args.get(ARG_ID) else {
+                return Ok(());
+            };
+            let Some(Arg::Msg(resolution)) = args.get(ARG_RESOLUTION) else {
+                return Ok(());
+            };
+            let Some(id) = parse_id(raw_id) else {
+                send_error(sender, "Usage: /reports close <id> <resolution>".to_string()).await;
+                return Ok(());
+            };
+
+            let staff = issuer_name(sender);
+            let resolution = resolution.trim().to_string();
+            let mut save_result = Ok(());
+            let result = {
+                let mut store = self.state.reports.write().unwrap();
+                let result = match store.get_mut(id) {
+                    None => Err(format!("No report with id #{id}.")),
+                    Some(entry) if entry.status == ReportStatus::Closed => {
+                        Err(format!("Report #{id} is already closed."))
+                    }
+                    Some(entry) => {
+                        entry.status = ReportStatus::Closed;
+                        entry.closed_by = Some(staff.clone());
+                        entry.resolution = Some(resolution.clone());
+                        Ok(entry.clone())
+                    }
+                };
+                if result.is_ok() {
+                    save_result = save_reports(&reports_path(&self.state.data_dir), &store);
+                }
+                result
+            };
+            if let Err(err) = save_result {
+                log::warn!("Failed to save reports.yml: {err}");
+            }
+
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(text) => {
+                    send_error(sender, text).await;
+                    return Ok(());
+                }
+            };
+
+            let msg = branding::brand(
+                TextComponent::text(format!(
+                    "{staff} closed report #{id} ({}): {resolution}",
+                    entry.target
+                ))
+                .color_named(NamedColor::Gold),
+            );
+            notify_staff(server, PERMISSION_REPORTS, &msg).await;
+            if sender.as_player().is_none() {
+                sender.send_message(msg).await;
+            }
+
+            if let Some(reporter) = server.get_player_by_uuid(entry.reporter_uuid).await {
+                let notice = branding::brand(
+                    TextComponent::text(format!(
+                        "Your report #{id} against {} was closed: {resolution}",
+                        entry.target
+                    ))
+                    .color_named(NamedColor::Green),
+                );
+                reporter.send_system_message(&notice).await;
+            }
+            Ok(())
+        })
+    }
+}
+
+pub fn report_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["report"], "Report a player to staff.").then(
+        require(|sender| sender.is_player()).then(
+            argument(ARG_NAME, SimpleArgConsumer).then(
+                argument(ARG_REASON, MsgArgConsumer).execute(ReportExecutor { state }),
+            ),
+        ),
+    )
+}
+
+pub fn reports_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["reports"], "List open player reports.")
+        .then(
+            literal("claim").then(argument(ARG_ID, SimpleArgConsumer).execute(state.audit.wrap(
+                "reports claim",
+                ReportClaimExecutor {
+                    state: Arc::clone(&state),
+                },
+            ))),
+        )
+        .then(
+            literal("close").then(
+                argument(ARG_ID, SimpleArgConsumer).then(
+                    argument(ARG_RESOLUTION, MsgArgConsumer).execute(state.audit.wrap(
+                        "reports close",
+                        ReportCloseExecutor {
+                            state: Arc::clone(&state),
+                        },
+                    )),
+                ),
+            ),
+        )
+        .execute(ReportsExecutor { state })
+}
@@ -11,6 +11,7 @@ use pumpkin_util::text::{TextComponent, color::NamedColor};
 
 use crate::{PluginState, branding};
 use crate::seen::{find_by_name, format_duration};
+use crate::vanish::{is_vanished, sees_vanished};
 
 const ARG_NAME: &str = "player";
 
@@ -30,7 +31,16 @@ impl CommandExecutor for SeenExecutor {
                 return Ok(());
             };
 
-            if let Some(player) = server.get_player_by_name(name).await {
+            let online = match server.get_player_by_name(name).await {
+                Some(player)
+                    if is_vanished(&self.state.vanished, player.gameprofile.id)
+                        && !sees_vanished(sender).await =>
+                {
+                    None
+                }
+                online => online,
+            };
+            if let Some(player) = online {
                 let world = player.world();
                 let dim = world.dimension.minecraft_name;
                 let msg = branding::brand(
@@ -7,6 +7,7 @@ use pumpkin::command::{
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const ARG_MODE: &str = "mode";
@@ -97,14 +98,17 @@ impl CommandExecutor for SpeedExecutor {
     }
 }
 
-pub fn speed_command_tree() -> CommandTree {
+pub fn speed_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["speed"], "Set walk or fly speed.")
         .then(
             argument(ARG_MODE, pumpkin::command::args::simple::SimpleArgConsumer)
                 .then(
                     argument(ARG_VALUE, value_consumer())
-                        .execute(SpeedExecutor)
-                        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(SpeedExecutor)),
+                        .execute(audit.wrap("speed", SpeedExecutor))
+                        .then(
+                            argument(ARG_TARGET, PlayersArgumentConsumer)
+                                .execute(audit.wrap("speed", SpeedExecutor)),
+                        ),
                 ),
         )
 }
@@ -17,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{branding, PluginState};
+use crate::audit::mark_failed;
 
 const STARTER_KIT_FILE_NAME: &str = "starterkit.yml";
 
@@ -167,6 +168,7 @@ impl CommandExecutor for StarterKitExecutor {
                     TextComponent::text(format!("Failed to save starterkit.yml: {err}"))
                         .color_named(NamedColor::Red),
                 );
+                mark_failed();
                 sender.send_message(msg).await;
                 return Ok(());
             }
@@ -196,6 +198,7 @@ impl CommandExecutor for DeleteStarterKitExecutor {
                         TextComponent::text(format!("Failed to delete starterkit.yml: {err}"))
                             .color_named(NamedColor::Red),
                     );
+                    mark_failed();
                     sender.send_message(msg).await;
                     return Ok(());
                 }
@@ -288,10 +291,20 @@ pub async fn apply_starterkit(player: &Arc<Player>, data_dir: &Path) {
 
 pub fn starterkit_command_tree(state: Arc<PluginState>) -> CommandTree {
     CommandTree::new(["starterkit"], "Set the starter kit from your inventory.")
-        .then(require(|sender| sender.is_player()).execute(StarterKitExecutor { state }))
+        .then(require(|sender| sender.is_player()).execute(state.audit.wrap(
+            "starterkit",
+            StarterKitExecutor {
+                state: Arc::clone(&state),
+            },
+        )))
 }
 
 pub fn delstarterkit_command_tree(state: Arc<PluginState>) -> CommandTree {
     CommandTree::new(["delstarterkit"], "Delete the starter kit.")
-        .execute(DeleteStarterKitExecutor { state })
+        .execute(state.audit.wrap(
+            "delstarterkit",
+            DeleteStarterKitExecutor {
+                state: Arc::clone(&state),
+            },
+        ))
 }
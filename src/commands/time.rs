@@ -5,6 +5,7 @@ use pumpkin::command::{
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const DAY_TIME: i64 = 1000;
@@ -63,12 +64,12 @@ impl CommandExecutor for NightExecutor {
     }
 }
 
-pub fn day_command_tree() -> CommandTree {
+pub fn day_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["day"], "Set time to day.")
-        .execute(DayExecutor)
+        .execute(audit.wrap("day", DayExecutor))
 }
 
-pub fn night_command_tree() -> CommandTree {
+pub fn night_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["night"], "Set time to night.")
-        .execute(NightExecutor)
+        .execute(audit.wrap("night", NightExecutor))
 }
@@ -16,20 +16,14 @@ impl CommandExecutor for TpsExecutor {
         _args: &'a ConsumedArgs<'a>,
     ) -> CommandResult<'a> {
         Box::pin(async move {
-            let avg_nanos = server.get_average_tick_time_nanos();
-            if avg_nanos <= 0 {
+            let Some((tps_display, mspt, target)) = tps_summary(server) else {
                 sender
                     .send_message(branding::brand(
                         TextComponent::text("TPS: N/A").color_named(NamedColor::Red),
                     ))
                     .await;
                 return Ok(());
-            }
-
-            let mspt = avg_nanos as f64 / 1_000_000.0;
-            let tps = 1_000_000_000.0 / avg_nanos as f64;
-            let target = server.tick_rate_manager.tickrate() as f64;
-            let tps_display = tps.min(target);
+            };
 
             let message = TextComponent::text(format!(
                 "TPS: {tps_display:.2} (MSPT: {mspt:.2}) Target: {target:.1}"
@@ -42,6 +36,19 @@ impl CommandExecutor for TpsExecutor {
     }
 }
 
+/// Current TPS (capped at the target rate), MSPT and target TPS, or `None` before the first tick.
+pub fn tps_summary(server: &Server) -> Option<(f64, f64, f64)> {
+    let avg_nanos = server.get_average_tick_time_nanos();
+    if avg_nanos <= 0 {
+        return None;
+    }
+
+    let mspt = avg_nanos as f64 / 1_000_000.0;
+    let tps = 1_000_000_000.0 / avg_nanos as f64;
+    let target = server.tick_rate_manager.tickrate() as f64;
+    Some((tps.min(target), mspt, target))
+}
+
 pub fn tps_command_tree() -> CommandTree {
     CommandTree::new(["tps"], "Show server TPS.").execute(TpsExecutor)
 }
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender, args::ConsumedArgs, tree::CommandTree,
+    tree::builder::require,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::vanish::{apply_visibility, save_vanished, vanish_path};
+
+struct VanishExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for VanishExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        _args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(player) = sender.as_player() else {
+                return Ok(());
+            };
+
+            let uuid = player.gameprofile.id;
+            let (hidden, save_result) = {
+                let mut vanished = self.state.vanished.write().unwrap();
+                let hidden = if vanished.remove(&uuid) {
+                    false
+                } else {
+                    vanished.insert(uuid);
+                    true
+                };
+                (
+                    hidden,
+                    save_vanished(&vanish_path(&self.state.data_dir), &vanished),
+                )
+            };
+            if let Err(err) = save_result {
+                log::warn!("Failed to save vanished.yml: {err}");
+            }
+
+            apply_visibility(server, player, hidden).await;
+
+            let msg = if hidden {
+                branding::brand(
+                    TextComponent::text("You are now vanished.").color_named(NamedColor::Green),
+                )
+            } else {
+                branding::brand(
+                    TextComponent::text("You are visible again.").color_named(NamedColor::Yellow),
+                )
+            };
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn vanish_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["vanish"], "Toggle vanish mode.").then(
+        require(|sender| sender.is_player()).execute(state.audit.wrap(
+            "vanish",
+            VanishExecutor {
+                state: Arc::clone(&state),
+            },
+        )),
+    )
+}
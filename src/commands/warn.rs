@@ -0,0 +1,251 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use pumpkin::command::{
+    CommandExecutor, CommandResult, CommandSender,
+    args::{Arg, ConsumedArgs, message::MsgArgConsumer, simple::SimpleArgConsumer},
+    tree::CommandTree,
+    tree::builder::argument,
+};
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{PluginState, branding};
+use crate::audit::mark_failed;
+use crate::commands::moderation_common::{issuer_name, resolve_player};
+use crate::seen::{format_duration, unix_now};
+use crate::warnings::{apply_thresholds, save_warnings, warning_notice, warnings_path};
+
+const ARG_NAME: &str = "player";
+const ARG_REASON: &str = "reason";
+const ARG_ID: &str = "id";
+
+struct WarnExecutor {
+    state: Arc<PluginState>,
+}
+
+struct WarningsExecutor {
+    state: Arc<PluginState>,
+}
+
+struct DelWarnExecutor {
+    state: Arc<PluginState>,
+}
+
+impl CommandExecutor for WarnExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+            let Some(Arg::Msg(reason)) = args.get(ARG_REASON) else {
+                return Ok(());
+            };
+
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("No data for {name}."))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let (entry, total, save_result) = {
+                let mut store = self.state.warnings.write().unwrap();
+                let mut entry = store.add(
+                    target.uuid,
+                    target.name.clone(),
+                    issuer_name(sender),
+                    reason.trim().to_string(),
+                );
+                if target.online.is_some() {
+                    if let Some(stored) = store.warnings.iter_mut().find(|w| w.id == entry.id) {
+                        stored.delivered = true;
+                    }
+                    entry.delivered = true;
+                }
+                let total = store.for_player(target.uuid).len();
+                let save_result = save_warnings(&warnings_path(&self.state.data_dir), &store);
+                (entry, total, save_result)
+            };
+            if let Err(err) = save_result {
+                let msg = branding::brand(
+                    TextComponent::text(format!("Failed to save warnings.yml: {err}"))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            if let Some(player) = &target.online {
+                player.send_system_message(&warning_notice(&entry)).await;
+            }
+
+            let msg = branding::brand(
+                TextComponent::text(format!(
+                    "Warned {} (#{}, {total} total). Reason: {}",
+                    target.name, entry.id, entry.reason
+                ))
+                .color_named(NamedColor::Green),
+            );
+            sender.send_message(msg).await;
+
+            let config = self.state.config.read().unwrap().clone();
+            apply_thresholds(
+                server,
+                &config,
+                &self.state.warnings,
+                target.uuid,
+                &target.name,
+            )
+            .await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for WarningsExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
+                return Ok(());
+            };
+
+            let Some(target) = resolve_player(server, &self.state, name).await else {
+                let msg = branding::brand(
+                    TextComponent::text(format!("No data for {name}."))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let entries = self.state.warnings.read().unwrap().for_player(target.uuid);
+            if entries.is_empty() {
+                let msg = branding::brand(
+                    TextComponent::text(format!("{} has no warnings.", target.name))
+                        .color_named(NamedColor::Green),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let now = unix_now();
+            let mut lines = format!("Warnings for {} ({}):", target.name, entries.len());
+            for entry in entries.iter().rev() {
+                let age = Duration::from_secs(now.saturating_sub(entry.issued_at));
+                lines.push_str(&format!(
+                    "\n#{} - {} ago by {}: {}",
+                    entry.id,
+                    format_duration(age),
+                    entry.issuer,
+                    entry.reason
+                ));
+            }
+
+            let body = TextComponent::text(lines).color_named(NamedColor::White);
+            sender.send_message(branding::brand(body)).await;
+            Ok(())
+        })
+    }
+}
+
+impl CommandExecutor for DelWarnExecutor {
+    fn execute<'a>(
+        &'a self,
+        sender: &'a CommandSender,
+        _server: &'a Server,
+        args: &'a ConsumedArgs<'a>,
+    ) -> CommandResult<'a> {
+        Box::pin(async move {
+            let Some(Arg::Simple(raw_id)) = args.get(ARG_ID) else {
+                return Ok(());
+            };
+            let Ok(id) = raw_id.trim_start_matches('#').parse::<u64>() else {
+                let msg = branding::brand(
+                    TextComponent::text("Usage: /delwarn <id>").color_named(NamedColor::Yellow),
+                );
+                sender.send_message(msg).await;
+                return Ok(());
+            };
+
+            let (removed, save_result) = {
+                let mut store = self.state.warnings.write().unwrap();
+                let index = store.warnings.iter().position(|entry| entry.id == id);
+                match index {
+                    Some(index) => {
+                        let removed = store.warnings.remove(index);
+                        let save_result =
+                            save_warnings(&warnings_path(&self.state.data_dir), &store);
+                        (Some(removed), save_result)
+                    }
+                    None => (None, Ok(())),
+                }
+            };
+
+            if let Err(err) = save_result {
+                let msg = branding::brand(
+                    TextComponent::text(format!("Failed to save warnings.yml: {err}"))
+                        .color_named(NamedColor::Red),
+                );
+                mark_failed();
+                sender.send_message(msg).await;
+                return Ok(());
+            }
+
+            let msg = match removed {
+                Some(entry) => branding::brand(
+                    TextComponent::text(format!("Removed warning #{id} from {}.", entry.name))
+                        .color_named(NamedColor::Green),
+                ),
+                None => branding::brand(
+                    TextComponent::text(format!("No warning with id #{id}."))
+                        .color_named(NamedColor::Yellow),
+                ),
+            };
+            sender.send_message(msg).await;
+            Ok(())
+        })
+    }
+}
+
+pub fn warn_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["warn"], "Warn a player.").then(
+        argument(ARG_NAME, SimpleArgConsumer)
+            .then(argument(ARG_REASON, MsgArgConsumer).execute(state.audit.wrap(
+                "warn",
+                WarnExecutor {
+                    state: Arc::clone(&state),
+                },
+            ))),
+    )
+}
+
+pub fn warnings_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["warnings"], "List a player's warnings.")
+        .then(argument(ARG_NAME, SimpleArgConsumer).execute(WarningsExecutor { state }))
+}
+
+pub fn delwarn_command_tree(state: Arc<PluginState>) -> CommandTree {
+    CommandTree::new(["delwarn"], "Delete a warning.")
+        .then(argument(ARG_ID, SimpleArgConsumer).execute(state.audit.wrap(
+            "delwarn",
+            DelWarnExecutor {
+                state: Arc::clone(&state),
+            },
+        )))
+}
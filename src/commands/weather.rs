@@ -5,6 +5,7 @@ use pumpkin::command::{
 use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
+use crate::audit::AuditLog;
 use crate::branding;
 
 const WEATHER_DURATION: i32 = 12_000;
@@ -91,17 +92,17 @@ impl CommandExecutor for ThunderExecutor {
     }
 }
 
-pub fn rain_command_tree() -> CommandTree {
+pub fn rain_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["rain"], "Set weather to rain.")
-        .execute(RainExecutor)
+        .execute(audit.wrap("rain", RainExecutor))
 }
 
-pub fn clear_command_tree() -> CommandTree {
+pub fn clear_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["clear"], "Clear weather.")
-        .execute(ClearExecutor)
+        .execute(audit.wrap("clear", ClearExecutor))
 }
 
-pub fn thunder_command_tree() -> CommandTree {
+pub fn thunder_command_tree(audit: &AuditLog) -> CommandTree {
     CommandTree::new(["thunder"], "Set weather to thunder.")
-        .execute(ThunderExecutor)
+        .execute(audit.wrap("thunder", ThunderExecutor))
 }
@@ -10,6 +10,8 @@ use pumpkin::server::Server;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
 use crate::{PluginState, branding};
+use crate::commands::moderation_common::sender_has_permission;
+use crate::notes::{PERMISSION_NOTES, notes_summary};
 use crate::seen::{find_by_name, format_duration};
 
 const ARG_NAME: &str = "player";
@@ -29,6 +31,7 @@ impl CommandExecutor for WhoisExecutor {
             let Some(Arg::Simple(name)) = args.get(ARG_NAME) else {
                 return Ok(());
             };
+            let show_notes = sender_has_permission(sender, PERMISSION_NOTES).await;
 
             if let Some(player) = server.get_player_by_name(name).await {
                 let uuid = player.gameprofile.id;
@@ -36,10 +39,15 @@ impl CommandExecutor for WhoisExecutor {
                 let op = player.permission_lvl.load() as u8;
                 let dim = player.world().dimension.minecraft_name;
                 let address = player.client.address().await;
-                let body = TextComponent::text(format!(
+                let mut text = format!(
                     "Player: {name}\nUUID: {uuid}\nGamemode: {gm}\nOp level: {op}\nWorld: {dim}\nAddress: {address}"
-                ))
-                .color_named(NamedColor::White);
+                );
+                if show_notes {
+                    let notes = self.state.notes.read().unwrap();
+                    text.push('\n');
+                    text.push_str(&notes_summary(&notes, uuid));
+                }
+                let body = TextComponent::text(text).color_named(NamedColor::White);
                 sender.send_message(branding::brand(body)).await;
                 return Ok(());
             }
@@ -51,13 +59,18 @@ impl CommandExecutor for WhoisExecutor {
             if let Some(entry) = entry {
                 let elapsed =
                     SystemTime::now().duration_since(entry.last_seen).unwrap_or_default();
-                let body = TextComponent::text(format!(
+                let mut text = format!(
                     "Player: {}\nUUID: {}\nOnline: no\nLast seen: {} ago",
                     entry.name,
                     entry.uuid,
                     format_duration(elapsed)
-                ))
-                .color_named(NamedColor::Yellow);
+                );
+                if show_notes {
+                    let notes = self.state.notes.read().unwrap();
+                    text.push('\n');
+                    text.push_str(&notes_summary(&notes, entry.uuid));
+                }
+                let body = TextComponent::text(text).color_named(NamedColor::Yellow);
                 sender.send_message(branding::brand(body)).await;
             } else {
                 let msg = branding::brand(
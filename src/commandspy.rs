@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+use pumpkin::entity::player::Player;
+use pumpkin::plugin::{BoxFuture, Cancellable, EventHandler};
+use pumpkin::plugin::events::player::player_command_send::PlayerCommandSendEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use uuid::Uuid;
+
+use crate::config::{Config, config_pairs};
+use crate::mutes::command_root;
+
+pub const PERMISSION_COMMANDSPY: &str = "JinxCore:commandspy";
+pub const PERMISSION_BLOCKED_COMMANDS_BYPASS: &str = "JinxCore:blockedcommands.bypass";
+
+/// Staff with command spy switched on. Kept in memory, so spying ends on restart.
+pub type CommandSpies = Arc<RwLock<HashSet<Uuid>>>;
+
+pub fn matches_root(list: &[String], root: &str) -> bool {
+    // Entries may be written namespaced (`pumpkin:give`) or bare; both block every variant.
+    list.iter().any(|entry| command_root(entry) == root)
+}
+
+/// Whether `player` may run a blocked command. `None` means `root` is not blocked at all.
+async fn blocked_command_allowed(config: &Config, player: &Player, root: &str) -> Option<bool> {
+    let nodes = config_pairs(&config.blocked_commands_permissions)
+        .into_iter()
+        .filter(|(_, command)| command_root(command) == root)
+        .map(|(node, _)| node)
+        .collect::<Vec<_>>();
+    if nodes.is_empty() && !matches_root(&config.blocked_commands, root) {
+        return None;
+    }
+    if player.has_permission(PERMISSION_BLOCKED_COMMANDS_BYPASS).await {
+        return Some(true);
+    }
+    for node in nodes {
+        if player.has_permission(&node).await {
+            return Some(true);
+        }
+    }
+    Some(false)
+}
+
+/// Rejects commands on the `blocked_commands` list, and those in
+/// `blocked_commands_permissions` the player lacks the permission for, before they reach the
+/// dispatcher.
+pub struct BlockedCommandHandler {
+    config: Arc<RwLock<Config>>,
+}
+
+impl BlockedCommandHandler {
+    pub fn new(config: Arc<RwLock<Config>>) -> Self {
+        Self { config }
+    }
+}
+
+impl EventHandler<PlayerCommandSendEvent> for BlockedCommandHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerCommandSendEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+            let root = command_root(&event.command);
+            if blocked_command_allowed(&config, &event.player, &root).await != Some(false) {
+                return;
+            }
+
+            event.set_cancelled(true);
+            let message = TextComponent::text(config.blocked_commands_message.clone())
+                .color_named(NamedColor::Red);
+            event.player.send_system_message(&message).await;
+        })
+    }
+}
+
+/// Shows commands that made it past the other command handlers to staff with spy enabled.
+pub struct CommandSpyHandler {
+    config: Arc<RwLock<Config>>,
+    spies: CommandSpies,
+}
+
+impl CommandSpyHandler {
+    pub fn new(config: Arc<RwLock<Config>>, spies: CommandSpies) -> Self {
+        Self { config, spies }
+    }
+}
+
+impl EventHandler<PlayerCommandSendEvent> for CommandSpyHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        server: &'a Arc<Server>,
+        event: &'a mut PlayerCommandSendEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if event.cancelled() {
+                return;
+            }
+            let spies = self.spies.read().unwrap().clone();
+            if spies.is_empty() {
+                return;
+            }
+            let hidden = {
+                let config = self.config.read().unwrap();
+                matches_root(&config.commandspy_hidden_commands, &command_root(&event.command))
+            };
+            if hidden {
+                return;
+            }
+
+            let source = event.player.gameprofile.id;
+            let message = TextComponent::text(format!(
+                "[Spy] {}: /{}",
+                event.player.gameprofile.name,
+                event.command.trim_start_matches('/')
+            ))
+            .color_named(NamedColor::Gray);
+            for uuid in spies {
+                if uuid == source {
+                    continue;
+                }
+                let Some(spy) = server.get_player_by_uuid(uuid).await else {
+                    continue;
+                };
+                if spy.has_permission(PERMISSION_COMMANDSPY).await {
+                    spy.send_system_message(&message).await;
+                }
+            }
+        })
+    }
+}
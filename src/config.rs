@@ -11,6 +11,54 @@ pub enum ChatFilterMode {
     Cancel,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvertAction {
+    Allow,
+    Replace,
+    Cancel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FreezeNotice {
+    Title,
+    ActionBar,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WarnThreshold {
+    pub count: usize,
+    pub within_days: u64,
+    pub commands: Vec<String>,
+}
+
+/// Channel IDs per Discord event type. An empty list falls back to the bridge channel, or to
+/// `audit_discord_channel_id` for moderation.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscordRoutes {
+    pub chat: Vec<u64>,
+    pub join: Vec<u64>,
+    pub leave: Vec<u64>,
+    pub death: Vec<u64>,
+    pub staff_alert: Vec<u64>,
+    pub moderation: Vec<u64>,
+    pub server: Vec<u64>,
+}
+
+/// A Discord channel relayed in-game besides the bridge channel.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiscordInboundChannel {
+    pub channel_id: u64,
+    /// Empty uses `discord_to_mc_format`.
+    #[serde(default)]
+    pub format: String,
+    /// Only players with this permission see the relayed messages; empty means everyone.
+    #[serde(default)]
+    pub permission: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
@@ -27,28 +75,92 @@ pub struct Config {
     pub webhook_send_chat: bool,
     pub webhook_send_join: bool,
     pub webhook_send_leave: bool,
+    pub webhook_send_death: bool,
+    pub webhook_send_lifecycle: bool,
     pub webhook_use_player_name: bool,
     pub webhook_avatar_url: String,
     pub webhook_chat_format: String,
     pub webhook_join_format: String,
     pub webhook_leave_format: String,
+    pub webhook_death_format: String,
     pub discord_bot_enabled: bool,
     pub discord_bot_token: String,
     pub discord_bot_channel_id: u64,
     pub discord_chat_format: String,
     pub discord_join_format: String,
     pub discord_leave_format: String,
+    pub discord_death_format: String,
+    pub discord_bot_use_webhook: bool,
+    pub discord_webhook_chat_format: String,
     pub discord_to_mc_format: String,
+    pub discord_to_mc_max_length: usize,
+    pub discord_routes: DiscordRoutes,
+    pub discord_inbound_channels: Vec<DiscordInboundChannel>,
+    pub discord_status_enabled: bool,
+    pub discord_status_interval_seconds: u64,
+    pub discord_topic_format: String,
+    pub discord_presence_format: String,
+    pub discord_offline_topic: String,
+    pub discord_console_channel_id: u64,
+    pub discord_console_allowed_ids: Vec<u64>,
+    pub discord_console_log_file: String,
+    pub discord_send_lifecycle: bool,
+    pub lifecycle_started_format: String,
+    pub lifecycle_stopping_format: String,
+    pub lifecycle_unexpected_format: String,
+    pub link_code_expiry_seconds: u64,
+    pub discord_linked_role_id: u64,
+    pub discord_permission_roles: Vec<String>,
+    pub discord_role_commands: Vec<String>,
+    pub discord_role_removed_commands: Vec<String>,
     pub antispam_enabled: bool,
     pub antispam_window_ms: u64,
     pub antispam_max_messages: usize,
     pub antispam_mute_seconds: u64,
     pub antispam_notify_message: String,
+    pub chat_locked_message: String,
+    pub chat_slow_message: String,
     pub chatfilter_enabled: bool,
     pub chatfilter_mode: ChatFilterMode,
     pub chatfilter_replacement: String,
     pub chatfilter_notify_message: String,
     pub chatfilter_words: Vec<String>,
+    pub advert_enabled: bool,
+    pub advert_url_action: AdvertAction,
+    pub advert_domain_action: AdvertAction,
+    pub advert_ip_action: AdvertAction,
+    pub advert_invite_action: AdvertAction,
+    pub advert_replacement: String,
+    pub advert_notify_message: String,
+    pub advert_allowed_domains: Vec<String>,
+    pub mute_message: String,
+    pub mute_blocked_commands: Vec<String>,
+    pub ban_screen: String,
+    pub ipban_screen: String,
+    pub ban_permanent_label: String,
+    pub warn_thresholds: Vec<WarnThreshold>,
+    pub alts_join_alert: bool,
+    pub alts_hash_addresses: bool,
+    pub audit_enabled: bool,
+    pub audit_max_file_kb: u64,
+    pub audit_max_files: usize,
+    pub audit_discord_channel_id: u64,
+    pub freeze_message: String,
+    pub freeze_notice: FreezeNotice,
+    pub freeze_allowed_commands: Vec<String>,
+    pub report_cooldown_seconds: u64,
+    pub report_format: String,
+    pub report_send_discord: bool,
+    pub report_send_webhook: bool,
+    pub blocked_commands: Vec<String>,
+    pub blocked_commands_permissions: Vec<String>,
+    pub blocked_commands_message: String,
+    pub commandspy_hidden_commands: Vec<String>,
+    pub joinflood_enabled: bool,
+    pub joinflood_max_joins_per_second: usize,
+    pub joinflood_lockdown_seconds: u64,
+    pub joinflood_kick_message: String,
+    pub inventory_snapshot_limit: usize,
     pub scoreboard_enabled: bool,
     pub scoreboard_title: String,
     pub scoreboard_update_interval_sec: u64,
@@ -70,28 +182,127 @@ impl Default for Config {
             webhook_send_chat: true,
             webhook_send_join: true,
             webhook_send_leave: true,
+            webhook_send_death: true,
+            webhook_send_lifecycle: true,
             webhook_use_player_name: true,
             webhook_avatar_url: "https://mc-heads.net/avatar/{PLAYER}".to_string(),
             webhook_chat_format: "{PLAYER}: {MESSAGE}".to_string(),
             webhook_join_format: "{PLAYER} joined the server.".to_string(),
             webhook_leave_format: "{PLAYER} left the server.".to_string(),
+            webhook_death_format: "{MESSAGE}".to_string(),
             discord_bot_enabled: false,
             discord_bot_token: String::new(),
             discord_bot_channel_id: 0,
             discord_chat_format: "{PLAYER}: {MESSAGE}".to_string(),
             discord_join_format: "{PLAYER} joined the server.".to_string(),
             discord_leave_format: "{PLAYER} left the server.".to_string(),
+            discord_death_format: "{MESSAGE}".to_string(),
+            discord_bot_use_webhook: false,
+            discord_webhook_chat_format: "{MESSAGE}".to_string(),
             discord_to_mc_format: "[Discord] {USER}: {MESSAGE}".to_string(),
+            discord_to_mc_max_length: 256,
+            discord_routes: DiscordRoutes::default(),
+            discord_inbound_channels: Vec::new(),
+            discord_status_enabled: false,
+            discord_status_interval_seconds: 600,
+            discord_topic_format: "%online%/%max% online | TPS %tps% | up %uptime%".to_string(),
+            discord_presence_format: "%online%/%max% online".to_string(),
+            discord_offline_topic: "Server offline".to_string(),
+            discord_console_channel_id: 0,
+            discord_console_allowed_ids: Vec::new(),
+            discord_console_log_file: "logs/latest.log".to_string(),
+            discord_send_lifecycle: true,
+            lifecycle_started_format: "Server started (Minecraft {VERSION}, loaded in {LOAD_TIME})."
+                .to_string(),
+            lifecycle_stopping_format: "Server stopping.".to_string(),
+            lifecycle_unexpected_format:
+                "Unexpected shutdown detected: the server did not stop cleanly last time."
+                    .to_string(),
+            link_code_expiry_seconds: 300,
+            discord_linked_role_id: 0,
+            discord_permission_roles: Vec::new(),
+            discord_role_commands: Vec::new(),
+            discord_role_removed_commands: Vec::new(),
             antispam_enabled: true,
             antispam_window_ms: 3000,
             antispam_max_messages: 5,
             antispam_mute_seconds: 5,
             antispam_notify_message: "Please slow down.".to_string(),
+            chat_locked_message: "Chat is currently locked.".to_string(),
+            chat_slow_message: "Slow mode is on. Wait {SECONDS}s before chatting again."
+                .to_string(),
             chatfilter_enabled: true,
             chatfilter_mode: ChatFilterMode::Replace,
             chatfilter_replacement: "****".to_string(),
             chatfilter_notify_message: "Please keep chat clean.".to_string(),
             chatfilter_words: vec!["badword".to_string()],
+            advert_enabled: true,
+            advert_url_action: AdvertAction::Replace,
+            advert_domain_action: AdvertAction::Replace,
+            advert_ip_action: AdvertAction::Cancel,
+            advert_invite_action: AdvertAction::Cancel,
+            advert_replacement: "<link removed>".to_string(),
+            advert_notify_message: "Advertising is not allowed.".to_string(),
+            advert_allowed_domains: Vec::new(),
+            mute_message: "You are muted {REMAINING}. Reason: {REASON}".to_string(),
+            mute_blocked_commands: ["msg", "tell", "w", "whisper", "me", "say", "teammsg", "tm"]
+                .iter()
+                .map(|command| command.to_string())
+                .collect(),
+            ban_screen: "You are banned from this server.\n\
+Reason: {REASON}\n\
+Expires: {EXPIRES}\n\
+Banned by: {ISSUER}"
+                .to_string(),
+            ipban_screen: "Your address is banned from this server.\n\
+Reason: {REASON}\n\
+Expires: {EXPIRES}"
+                .to_string(),
+            ban_permanent_label: "Never".to_string(),
+            warn_thresholds: vec![WarnThreshold {
+                count: 3,
+                within_days: 7,
+                commands: vec!["tempmute {PLAYER} 1d Reached {COUNT} warnings".to_string()],
+            }],
+            alts_join_alert: true,
+            alts_hash_addresses: false,
+            audit_enabled: true,
+            audit_max_file_kb: 1024,
+            audit_max_files: 5,
+            audit_discord_channel_id: 0,
+            freeze_message: "You are frozen. Do not log out and follow staff instructions."
+                .to_string(),
+            freeze_notice: FreezeNotice::Title,
+            freeze_allowed_commands: ["msg", "tell", "w", "r", "reply"]
+                .iter()
+                .map(|command| command.to_string())
+                .collect(),
+            report_cooldown_seconds: 60,
+            report_format: "Report #{ID}: {PLAYER} reported {TARGET} for {REASON}".to_string(),
+            report_send_discord: true,
+            report_send_webhook: true,
+            blocked_commands: Vec::new(),
+            blocked_commands_permissions: Vec::new(),
+            blocked_commands_message: "You do not have permission to use that command."
+                .to_string(),
+            commandspy_hidden_commands: [
+                "login",
+                "l",
+                "register",
+                "reg",
+                "changepassword",
+                "changepass",
+                "unregister",
+            ]
+            .iter()
+            .map(|command| command.to_string())
+            .collect(),
+            joinflood_enabled: true,
+            joinflood_max_joins_per_second: 5,
+            joinflood_lockdown_seconds: 120,
+            joinflood_kick_message: "Server is in lockdown. Please try again in a few minutes."
+                .to_string(),
+            inventory_snapshot_limit: 10,
             scoreboard_enabled: true,
             scoreboard_title: "JinxCore".to_string(),
             scoreboard_update_interval_sec: 5,
@@ -119,6 +330,40 @@ fn serialize_color(color: &NamedColor) -> String {
         .to_string()
 }
 
+fn yaml_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("  - \"{}\"", yaml_escape(value)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn yaml_routes(routes: &DiscordRoutes) -> String {
+    [
+        ("chat", &routes.chat),
+        ("join", &routes.join),
+        ("leave", &routes.leave),
+        ("death", &routes.death),
+        ("staff_alert", &routes.staff_alert),
+        ("moderation", &routes.moderation),
+        ("server", &routes.server),
+    ]
+    .iter()
+    .map(|(name, ids)| {
+        let ids = ids.iter().map(u64::to_string).collect::<Vec<_>>();
+        format!("  {name}: [{}]", ids.join(", "))
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn serialize_advert_action(action: &AdvertAction) -> String {
+    serde_yaml::to_string(action)
+        .unwrap_or_else(|_| "replace".to_string())
+        .trim()
+        .to_string()
+}
+
 fn default_config_yaml() -> String {
     let d = Config::default();
     format!(
@@ -140,11 +385,15 @@ webhook_url: \"{webhook_url}\"\n\
 webhook_send_chat: {webhook_send_chat}\n\
 webhook_send_join: {webhook_send_join}\n\
 webhook_send_leave: {webhook_send_leave}\n\
+webhook_send_death: {webhook_send_death}\n\
+webhook_send_lifecycle: {webhook_send_lifecycle}\n\
 webhook_use_player_name: {webhook_use_player_name}\n\
 webhook_avatar_url: \"{webhook_avatar_url}\"\n\
 webhook_chat_format: \"{webhook_chat_format}\"\n\
 webhook_join_format: \"{webhook_join_format}\"\n\
 webhook_leave_format: \"{webhook_leave_format}\"\n\
+# Death formats also accept {{KILLER}}; {{MESSAGE}} is the vanilla death message\n\
+webhook_death_format: \"{webhook_death_format}\"\n\
 \n\
 # Discord Bot\n\
 discord_bot_enabled: {discord_bot_enabled}\n\
@@ -153,7 +402,56 @@ discord_bot_channel_id: {discord_bot_channel_id}\n\
 discord_chat_format: \"{discord_chat_format}\"\n\
 discord_join_format: \"{discord_join_format}\"\n\
 discord_leave_format: \"{discord_leave_format}\"\n\
+discord_death_format: \"{discord_death_format}\"\n\
+# Send chat through a webhook in the bridge channel (created by the bot, needs Manage\n\
+# Webhooks) using the player's name and webhook_avatar_url\n\
+discord_bot_use_webhook: {discord_bot_use_webhook}\n\
+discord_webhook_chat_format: \"{discord_webhook_chat_format}\"\n\
 discord_to_mc_format: \"{discord_to_mc_format}\"\n\
+# Longer Discord messages are cut off in-game (characters, 0 = no limit)\n\
+discord_to_mc_max_length: {discord_to_mc_max_length}\n\
+# Channel IDs per event type. Empty lists use the bridge channel (moderation uses\n\
+# audit_discord_channel_id). Advancements are not relayed, so they have no route.\n\
+discord_routes:\n\
+{discord_routes}\n\
+# More Discord channels relayed in-game, each with its own format and an optional\n\
+# permission needed to see them, e.g.\n\
+#   - channel_id: 123456789012345678\n\
+#     format: \"[Staff] {{USER}}: {{MESSAGE}}\"\n\
+#     permission: \"JinxCore:staffchat\"\n\
+discord_inbound_channels: []\n\
+# Live bridge channel topic and bot presence (same placeholders as scoreboard.txt).\n\
+# The interval is at least 300 seconds because Discord rate-limits topic edits.\n\
+discord_status_enabled: {discord_status_enabled}\n\
+discord_status_interval_seconds: {discord_status_interval_seconds}\n\
+discord_topic_format: \"{discord_topic_format}\"\n\
+discord_presence_format: \"{discord_presence_format}\"\n\
+discord_offline_topic: \"{discord_offline_topic}\"\n\
+# Console channel (0 = disabled): messages from the allowed user or role IDs run as console\n\
+# commands, and new lines of the log file are mirrored into it\n\
+discord_console_channel_id: {discord_console_channel_id}\n\
+discord_console_allowed_ids: []\n\
+discord_console_log_file: \"{discord_console_log_file}\"\n\
+\n\
+# Server start/stop notices, sent by the bot (discord_routes.server) and the webhook\n\
+# (webhook_send_lifecycle). {{VERSION}} is the Minecraft version and {{LOAD_TIME}} the time\n\
+# from process start until JinxCore loaded. The unexpected shutdown notice is sent on start\n\
+# when the previous run did not stop cleanly. Empty formats are not sent.\n\
+discord_send_lifecycle: {discord_send_lifecycle}\n\
+lifecycle_started_format: \"{lifecycle_started_format}\"\n\
+lifecycle_stopping_format: \"{lifecycle_stopping_format}\"\n\
+lifecycle_unexpected_format: \"{lifecycle_unexpected_format}\"\n\
+\n\
+# Account linking (/link in-game, then /link <code> in Discord)\n\
+link_code_expiry_seconds: {link_code_expiry_seconds}\n\
+# Discord role given to every linked player (0 = disabled)\n\
+discord_linked_role_id: {discord_linked_role_id}\n\
+# \"permission=role_id\": the role follows whether the player holds the permission\n\
+discord_permission_roles: []\n\
+# \"role_id=command\": console command run once when a linked player gains the role, and\n\
+# when they lose it or unlink. Checked on join and link (placeholders: {{PLAYER}})\n\
+discord_role_commands: []\n\
+discord_role_removed_commands: []\n\
 \n\
 # Anti-spam\n\
 antispam_enabled: {antispam_enabled}\n\
@@ -162,6 +460,10 @@ antispam_max_messages: {antispam_max_messages}\n\
 antispam_mute_seconds: {antispam_mute_seconds}\n\
 antispam_notify_message: \"{antispam_notify_message}\"\n\
 \n\
+# Chat lock and slow mode (placeholders: {{SECONDS}})\n\
+chat_locked_message: \"{chat_locked_message}\"\n\
+chat_slow_message: \"{chat_slow_message}\"\n\
+\n\
 # Chat filter\n\
 chatfilter_enabled: {chatfilter_enabled}\n\
 chatfilter_mode: {chatfilter_mode}\n\
@@ -170,6 +472,82 @@ chatfilter_notify_message: \"{chatfilter_notify_message}\"\n\
 chatfilter_words:\n\
   - \"{chatfilter_word}\"\n\
 \n\
+# Advertising (actions: allow, replace, cancel)\n\
+# Links from socials.txt are always allowed.\n\
+advert_enabled: {advert_enabled}\n\
+advert_url_action: {advert_url_action}\n\
+advert_domain_action: {advert_domain_action}\n\
+advert_ip_action: {advert_ip_action}\n\
+advert_invite_action: {advert_invite_action}\n\
+advert_replacement: \"{advert_replacement}\"\n\
+advert_notify_message: \"{advert_notify_message}\"\n\
+advert_allowed_domains: []\n\
+\n\
+# Mutes\n\
+mute_message: \"{mute_message}\"\n\
+mute_blocked_commands:\n\
+{mute_blocked_commands}\n\
+\n\
+# Bans (placeholders: {{REASON}}, {{EXPIRES}}, {{ISSUER}})\n\
+ban_screen: \"{ban_screen}\"\n\
+ipban_screen: \"{ipban_screen}\"\n\
+ban_permanent_label: \"{ban_permanent_label}\"\n\
+\n\
+# Warnings: run console commands when a player reaches `count` warnings\n\
+# within `within_days` days (placeholders: {{PLAYER}}, {{COUNT}})\n\
+warn_thresholds:\n\
+{warn_thresholds}\n\
+\n\
+# Alt detection\n\
+# Alert staff when a player joins from an address used by a banned or muted account\n\
+alts_join_alert: {alts_join_alert}\n\
+# Store salted hashes instead of raw IP addresses. Addresses recorded before\n\
+# toggling this no longer match new joins.\n\
+alts_hash_addresses: {alts_hash_addresses}\n\
+\n\
+# Audit log of privileged commands (audit.log, rotated into audit.log.1, audit.log.2, ...)\n\
+audit_enabled: {audit_enabled}\n\
+audit_max_file_kb: {audit_max_file_kb}\n\
+audit_max_files: {audit_max_files}\n\
+# Mirror audit entries to this Discord channel through the bot (0 = disabled)\n\
+audit_discord_channel_id: {audit_discord_channel_id}\n\
+\n\
+# Freeze (freeze_notice: title or actionbar)\n\
+freeze_message: \"{freeze_message}\"\n\
+freeze_notice: {freeze_notice}\n\
+# Commands a frozen player may still run\n\
+freeze_allowed_commands:\n\
+{freeze_allowed_commands}\n\
+\n\
+# Player reports (placeholders: {{ID}}, {{PLAYER}}, {{TARGET}}, {{REASON}})\n\
+report_cooldown_seconds: {report_cooldown_seconds}\n\
+report_format: \"{report_format}\"\n\
+# Forward new reports through the Discord bot and/or the webhook\n\
+report_send_discord: {report_send_discord}\n\
+report_send_webhook: {report_send_webhook}\n\
+\n\
+# Commands players cannot run without JinxCore:blockedcommands.bypass.\n\
+# `give` also blocks `pumpkin:give` and other namespaced variants.\n\
+blocked_commands: []\n\
+# \"permission=command\": the command is blocked for everyone without that permission or\n\
+# the bypass. Several entries may name the same command.\n\
+blocked_commands_permissions: []\n\
+blocked_commands_message: \"{blocked_commands_message}\"\n\
+# Commands never shown to /commandspy (passwords and other secrets). Their arguments are\n\
+# also cut from log lines relayed to the Discord console channel.\n\
+commandspy_hidden_commands:\n\
+{commandspy_hidden_commands}\n\
+\n\
+# Join-flood protection: more joins than this per second starts a lockdown that kicks\n\
+# players missing from the seen database. Lifts after joinflood_lockdown_seconds of calm.\n\
+joinflood_enabled: {joinflood_enabled}\n\
+joinflood_max_joins_per_second: {joinflood_max_joins_per_second}\n\
+joinflood_lockdown_seconds: {joinflood_lockdown_seconds}\n\
+joinflood_kick_message: \"{joinflood_kick_message}\"\n\
+\n\
+# Inventory snapshots taken before /clearinv, kits, death and logout (per player)\n\
+inventory_snapshot_limit: {inventory_snapshot_limit}\n\
+\n\
 # Scoreboard\n\
 scoreboard_enabled: {scoreboard_enabled}\n\
 scoreboard_title: \"{scoreboard_title}\"\n\
@@ -187,23 +565,46 @@ scoreboard_update_interval_sec: {scoreboard_update_interval_sec}\n",
         webhook_send_chat = d.webhook_send_chat,
         webhook_send_join = d.webhook_send_join,
         webhook_send_leave = d.webhook_send_leave,
+        webhook_send_death = d.webhook_send_death,
+        webhook_send_lifecycle = d.webhook_send_lifecycle,
         webhook_use_player_name = d.webhook_use_player_name,
         webhook_avatar_url = yaml_escape(&d.webhook_avatar_url),
         webhook_chat_format = yaml_escape(&d.webhook_chat_format),
         webhook_join_format = yaml_escape(&d.webhook_join_format),
         webhook_leave_format = yaml_escape(&d.webhook_leave_format),
+        webhook_death_format = yaml_escape(&d.webhook_death_format),
         discord_bot_enabled = d.discord_bot_enabled,
         discord_bot_token = yaml_escape(&d.discord_bot_token),
         discord_bot_channel_id = d.discord_bot_channel_id,
         discord_chat_format = yaml_escape(&d.discord_chat_format),
         discord_join_format = yaml_escape(&d.discord_join_format),
         discord_leave_format = yaml_escape(&d.discord_leave_format),
+        discord_death_format = yaml_escape(&d.discord_death_format),
+        discord_bot_use_webhook = d.discord_bot_use_webhook,
+        discord_webhook_chat_format = yaml_escape(&d.discord_webhook_chat_format),
         discord_to_mc_format = yaml_escape(&d.discord_to_mc_format),
+        discord_to_mc_max_length = d.discord_to_mc_max_length,
+        discord_routes = yaml_routes(&d.discord_routes),
+        discord_status_enabled = d.discord_status_enabled,
+        discord_status_interval_seconds = d.discord_status_interval_seconds,
+        discord_topic_format = yaml_escape(&d.discord_topic_format),
+        discord_presence_format = yaml_escape(&d.discord_presence_format),
+        discord_offline_topic = yaml_escape(&d.discord_offline_topic),
+        discord_console_channel_id = d.discord_console_channel_id,
+        discord_console_log_file = yaml_escape(&d.discord_console_log_file),
+        discord_send_lifecycle = d.discord_send_lifecycle,
+        lifecycle_started_format = yaml_escape(&d.lifecycle_started_format),
+        lifecycle_stopping_format = yaml_escape(&d.lifecycle_stopping_format),
+        lifecycle_unexpected_format = yaml_escape(&d.lifecycle_unexpected_format),
+        link_code_expiry_seconds = d.link_code_expiry_seconds,
+        discord_linked_role_id = d.discord_linked_role_id,
         antispam_enabled = d.antispam_enabled,
         antispam_window_ms = d.antispam_window_ms,
         antispam_max_messages = d.antispam_max_messages,
         antispam_mute_seconds = d.antispam_mute_seconds,
         antispam_notify_message = yaml_escape(&d.antispam_notify_message),
+        chat_locked_message = yaml_escape(&d.chat_locked_message),
+        chat_slow_message = yaml_escape(&d.chat_slow_message),
         chatfilter_enabled = d.chatfilter_enabled,
         chatfilter_mode = serde_yaml::to_string(&d.chatfilter_mode)
             .unwrap_or_else(|_| "replace".to_string())
@@ -211,6 +612,43 @@ scoreboard_update_interval_sec: {scoreboard_update_interval_sec}\n",
         chatfilter_replacement = yaml_escape(&d.chatfilter_replacement),
         chatfilter_notify_message = yaml_escape(&d.chatfilter_notify_message),
         chatfilter_word = d.chatfilter_words.get(0).cloned().unwrap_or_else(|| "badword".to_string()),
+        advert_enabled = d.advert_enabled,
+        advert_url_action = serialize_advert_action(&d.advert_url_action),
+        advert_domain_action = serialize_advert_action(&d.advert_domain_action),
+        advert_ip_action = serialize_advert_action(&d.advert_ip_action),
+        advert_invite_action = serialize_advert_action(&d.advert_invite_action),
+        advert_replacement = yaml_escape(&d.advert_replacement),
+        advert_notify_message = yaml_escape(&d.advert_notify_message),
+        mute_message = yaml_escape(&d.mute_message),
+        mute_blocked_commands = yaml_list(&d.mute_blocked_commands),
+        ban_screen = yaml_escape(&d.ban_screen),
+        ipban_screen = yaml_escape(&d.ipban_screen),
+        ban_permanent_label = yaml_escape(&d.ban_permanent_label),
+        warn_thresholds = serde_yaml::to_string(&d.warn_thresholds)
+            .unwrap_or_default()
+            .trim_end(),
+        alts_join_alert = d.alts_join_alert,
+        alts_hash_addresses = d.alts_hash_addresses,
+        audit_enabled = d.audit_enabled,
+        audit_max_file_kb = d.audit_max_file_kb,
+        audit_max_files = d.audit_max_files,
+        audit_discord_channel_id = d.audit_discord_channel_id,
+        freeze_message = yaml_escape(&d.freeze_message),
+        freeze_notice = serde_yaml::to_string(&d.freeze_notice)
+            .unwrap_or_else(|_| "title".to_string())
+            .trim(),
+        freeze_allowed_commands = yaml_list(&d.freeze_allowed_commands),
+        report_cooldown_seconds = d.report_cooldown_seconds,
+        report_format = yaml_escape(&d.report_format),
+        report_send_discord = d.report_send_discord,
+        report_send_webhook = d.report_send_webhook,
+        blocked_commands_message = yaml_escape(&d.blocked_commands_message),
+        commandspy_hidden_commands = yaml_list(&d.commandspy_hidden_commands),
+        joinflood_enabled = d.joinflood_enabled,
+        joinflood_max_joins_per_second = d.joinflood_max_joins_per_second,
+        joinflood_lockdown_seconds = d.joinflood_lockdown_seconds,
+        joinflood_kick_message = yaml_escape(&d.joinflood_kick_message),
+        inventory_snapshot_limit = d.inventory_snapshot_limit,
         scoreboard_enabled = d.scoreboard_enabled,
         scoreboard_title = yaml_escape(&d.scoreboard_title),
         scoreboard_update_interval_sec = d.scoreboard_update_interval_sec,
@@ -232,3 +670,13 @@ pub fn load_or_create(data_dir: &Path) -> Result<Config, String> {
 
     Ok(config)
 }
+
+/// Parses `key=value` config entries, skipping malformed ones.
+pub fn config_pairs(entries: &[String]) -> Vec<(String, String)> {
+    entries
+        .iter()
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+        .collect()
+}
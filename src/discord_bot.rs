@@ -1,30 +1,79 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock, RwLock, mpsc as std_mpsc};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use serenity::async_trait;
-use serenity::model::channel::Message;
+use serenity::model::application::Interaction;
+use serenity::model::channel::{Channel, Message};
+use serenity::model::gateway::Ready;
+use serenity::http::Http;
 use serenity::model::id::ChannelId;
 use serenity::prelude::*;
 use tokio::sync::mpsc;
 
+use pumpkin::SHOULD_STOP;
 use pumpkin::server::Server;
 use pumpkin_util::text::TextComponent;
 
+use crate::PluginState;
 use crate::config::Config;
+use crate::discord_commands::{command_definitions, handle_command};
+use crate::discord_console::handle_console_message;
+use crate::discord_outbound::{
+    DiscordOutbound, GuildSlot, HttpSlot, MemberSync, OutboundStats, OutboundWorker,
+    QUEUE_CAPACITY, QueueStats, ShardSlot, StateSlot,
+};
+use crate::discord_render::render_message;
+use crate::webhook::DEATH_COLOR;
+
+const RECONNECT_MIN_DELAY: Duration = Duration::from_secs(5);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(300);
 
 #[derive(Clone)]
 pub struct DiscordBridge {
-    sender: mpsc::UnboundedSender<DiscordOutbound>,
+    sender: mpsc::Sender<DiscordOutbound>,
+    state: StateSlot,
+    stats: Arc<OutboundStats>,
+}
+
+impl DiscordBridge {
+    pub fn attach_state(&self, state: Arc<PluginState>) {
+        let _ = self.state.set(state);
+    }
+
+    pub fn queue_stats(&self) -> QueueStats {
+        let queued = QUEUE_CAPACITY - self.sender.capacity();
+        QueueStats::new(&self.stats, queued)
+    }
+
+    /// Queues a message without blocking; when the queue is full it is dropped and counted.
+    fn enqueue(&self, outbound: DiscordOutbound) -> bool {
+        let queued = self.sender.try_send(outbound).is_ok();
+        if !queued {
+            self.stats.record_dropped();
+        }
+        queued
+    }
 }
 
 pub enum DiscordEvent {
     Chat,
     Join,
     Leave,
+    Death { killer: Option<String> },
 }
 
-enum DiscordOutbound {
-    Message(String),
+/// Event types that can be sent to their own channels through `discord_routes`. Each variant
+/// has a field of the same name in `DiscordRoutes`; add both together.
+#[derive(Clone, Copy)]
+pub enum DiscordRoute {
+    Chat,
+    Join,
+    Leave,
+    Death,
+    StaffAlert,
+    Moderation,
+    Server,
 }
 
 pub fn start_discord_bot(config: &Config, server: Arc<Server>) -> Option<DiscordBridge> {
@@ -39,53 +88,76 @@ pub fn start_discord_bot(config: &Config, server: Arc<Server>) -> Option<Discord
         return None;
     }
 
-    let (tx, mut rx) = mpsc::unbounded_channel::<DiscordOutbound>();
+    let (tx, rx) = mpsc::channel::<DiscordOutbound>(QUEUE_CAPACITY);
     let channel_id = ChannelId::new(config.discord_bot_channel_id);
     let token = token.to_string();
     let to_mc_format = config.discord_to_mc_format.clone();
+    let to_mc_max_length = config.discord_to_mc_max_length;
+    let state: StateSlot = Arc::new(OnceLock::new());
+    let handler_state = Arc::clone(&state);
+    let guild: GuildSlot = Arc::new(OnceLock::new());
+    let shards: ShardSlot = Arc::new(RwLock::new(None));
+    let http: HttpSlot = Arc::new(RwLock::new(Arc::new(Http::new(&token))));
+    let stats = Arc::new(OutboundStats::default());
+
+    let worker = OutboundWorker::new(
+        Arc::clone(&http),
+        channel_id,
+        Arc::clone(&guild),
+        Arc::clone(&shards),
+        Arc::clone(&server),
+        Arc::clone(&state),
+        Arc::clone(&stats),
+    );
 
     thread::spawn(move || {
         let runtime = tokio::runtime::Runtime::new().expect("discord runtime");
         runtime.block_on(async move {
-            let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
-            let handler = DiscordHandler {
-                channel_id,
-                server,
-                to_mc_format,
-            };
-
-            let mut client = match Client::builder(&token, intents)
-                .event_handler(handler)
-                .await
-            {
-                Ok(client) => client,
-                Err(err) => {
-                    log::error!("Discord bot failed to start: {err}");
-                    return;
-                }
-            };
+            tokio::spawn(worker.run(rx));
 
-            let http = client.http.clone();
-            tokio::spawn(async move {
-                while let Some(outbound) = rx.recv().await {
-                    match outbound {
-                        DiscordOutbound::Message(content) => {
-                            let builder = serenity::builder::CreateMessage::new().content(content);
-                            let _ = channel_id
-                                .send_message(&http, builder)
-                                .await;
+            let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+            let mut delay = RECONNECT_MIN_DELAY;
+            loop {
+                let handler = DiscordHandler {
+                    channel_id,
+                    server: Arc::clone(&server),
+                    to_mc_format: to_mc_format.clone(),
+                    to_mc_max_length,
+                    state: Arc::clone(&handler_state),
+                    guild: Arc::clone(&guild),
+                };
+                let started = Instant::now();
+                match Client::builder(&token, intents).event_handler(handler).await {
+                    Ok(mut client) => {
+                        *http.write().unwrap() = Arc::clone(&client.http);
+                        *shards.write().unwrap() = Some(Arc::clone(&client.shard_manager));
+                        match client.start().await {
+                            Ok(()) => log::warn!("Discord gateway connection closed"),
+                            Err(err) => log::error!("Discord bot stopped: {err}"),
                         }
+                        *shards.write().unwrap() = None;
                     }
+                    Err(err) => log::error!("Discord bot failed to start: {err}"),
                 }
-            });
-
-            if let Err(err) = client.start().await {
-                log::error!("Discord bot stopped: {err}");
+                if SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
+                    break;
+                }
+                // A connection that stayed up for a while starts the backoff over.
+                if started.elapsed() > RECONNECT_MAX_DELAY {
+                    delay = RECONNECT_MIN_DELAY;
+                }
+                log::warn!("Reconnecting to Discord in {}s", delay.as_secs());
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
             }
         });
     });
 
-    Some(DiscordBridge { sender: tx })
+    Some(DiscordBridge {
+        sender: tx,
+        state,
+        stats,
+    })
 }
 
 pub fn send_discord_event(
@@ -101,13 +173,154 @@ pub fn send_discord_event(
     if !config.discord_bot_enabled {
         return;
     }
-    let template = match event {
-        DiscordEvent::Chat => &config.discord_chat_format,
-        DiscordEvent::Join => &config.discord_join_format,
-        DiscordEvent::Leave => &config.discord_leave_format,
+    let (template, route) = match &event {
+        DiscordEvent::Chat => (&config.discord_chat_format, DiscordRoute::Chat),
+        DiscordEvent::Join => (&config.discord_join_format, DiscordRoute::Join),
+        DiscordEvent::Leave => (&config.discord_leave_format, DiscordRoute::Leave),
+        DiscordEvent::Death { .. } => (&config.discord_death_format, DiscordRoute::Death),
+    };
+    let escaped_player = escape_markdown(player);
+    let escaped_message = message.map(escape_markdown);
+    let content = format_message(template, &escaped_player, escaped_message.as_deref());
+    let content = match &event {
+        DiscordEvent::Death { killer } => content.replace(
+            "{KILLER}",
+            &escape_markdown(killer.as_deref().unwrap_or_default()),
+        ),
+        _ => content,
+    };
+    let avatar_url = config.webhook_avatar_url.trim();
+    let avatar_url = if avatar_url.is_empty() {
+        None
+    } else {
+        Some(avatar_url.replace("{PLAYER}", player))
+    };
+
+    for channel in route_channels(config, route) {
+        let outbound = match &event {
+            DiscordEvent::Death { .. } => {
+                DiscordOutbound::Embed(channel, content.clone(), DEATH_COLOR)
+            }
+            DiscordEvent::Chat if config.discord_bot_use_webhook => DiscordOutbound::WebhookChat {
+                channel,
+                username: player.to_string(),
+                avatar_url: avatar_url.clone(),
+                content: format_message(
+                    &config.discord_webhook_chat_format,
+                    &escaped_player,
+                    escaped_message.as_deref(),
+                ),
+                fallback: content.clone(),
+            },
+            DiscordEvent::Chat => DiscordOutbound::Chat(channel, content.clone()),
+            _ => DiscordOutbound::ChannelMessage(channel, content.clone()),
+        };
+        bridge.enqueue(outbound);
+    }
+}
+
+/// Channels for an event type: the configured route, or the default channel when it is empty.
+fn route_channels(config: &Config, route: DiscordRoute) -> Vec<ChannelId> {
+    let routes = &config.discord_routes;
+    let (configured, fallback) = match route {
+        DiscordRoute::Chat => (&routes.chat, config.discord_bot_channel_id),
+        DiscordRoute::Join => (&routes.join, config.discord_bot_channel_id),
+        DiscordRoute::Leave => (&routes.leave, config.discord_bot_channel_id),
+        DiscordRoute::Death => (&routes.death, config.discord_bot_channel_id),
+        DiscordRoute::StaffAlert => (&routes.staff_alert, config.discord_bot_channel_id),
+        DiscordRoute::Moderation => (&routes.moderation, config.audit_discord_channel_id),
+        DiscordRoute::Server => (&routes.server, config.discord_bot_channel_id),
+    };
+    let ids = if configured.is_empty() {
+        vec![fallback]
+    } else {
+        configured.clone()
+    };
+    ids.into_iter()
+        .filter(|id| *id != 0)
+        .map(ChannelId::new)
+        .collect()
+}
+
+/// Sends a preformatted line to the channels of a route.
+pub fn send_discord_message(
+    bridge: Option<&DiscordBridge>,
+    config: &Config,
+    route: DiscordRoute,
+    content: String,
+) {
+    let Some(bridge) = bridge else {
+        return;
+    };
+    for channel in route_channels(config, route) {
+        bridge.enqueue(DiscordOutbound::ChannelMessage(channel, content.clone()));
+    }
+}
+
+/// Sends a message to a specific channel, e.g. the console channel.
+pub fn send_discord_channel(bridge: Option<&DiscordBridge>, channel_id: u64, content: String) {
+    let Some(bridge) = bridge else {
+        return;
+    };
+    if channel_id == 0 {
+        return;
+    }
+    bridge.enqueue(DiscordOutbound::ChannelMessage(ChannelId::new(channel_id), content));
+}
+
+/// Escapes Discord markdown in player-controlled text. Headings and list markers only count
+/// at the start of a line, so `#` and `-` are only escaped there.
+pub fn escape_markdown(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    if text.starts_with(['#', '-']) {
+        output.push('\\');
+    }
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '*' | '_' | '~' | '`' | '|' | '>' | '[' | ']') {
+            output.push('\\');
+        }
+        output.push(ch);
+    }
+    output
+}
+
+/// Updates the bridge channel topic and/or the bot presence.
+pub fn send_discord_status(
+    bridge: Option<&DiscordBridge>,
+    topic: Option<String>,
+    presence: Option<String>,
+) {
+    let Some(bridge) = bridge else {
+        return;
+    };
+    bridge.enqueue(DiscordOutbound::Status { topic, presence });
+}
+
+/// Blocks until everything queued so far has been sent, or the timeout passes. Used on
+/// shutdown so the last messages are not lost when the process exits.
+pub fn flush_discord(bridge: Option<&DiscordBridge>, timeout: Duration) -> bool {
+    let Some(bridge) = bridge else {
+        return true;
+    };
+    let (ack, done) = std_mpsc::channel();
+    if !bridge.enqueue(DiscordOutbound::Flush(ack)) {
+        return false;
+    }
+    done.recv_timeout(timeout).is_ok()
+}
+
+/// Queues a role sync for a linked member.
+pub fn send_member_sync(bridge: Option<&DiscordBridge>, sync: MemberSync) {
+    let Some(bridge) = bridge else {
+        return;
     };
-    let content = format_message(template, player, message);
-    let _ = bridge.sender.send(DiscordOutbound::Message(content));
+    if sync.roles.is_empty()
+        && sync.role_commands.is_empty()
+        && sync.role_removed_commands.is_empty()
+    {
+        return;
+    }
+    bridge.enqueue(DiscordOutbound::SyncMember(sync));
 }
 
 fn format_message(template: &str, player: &str, message: Option<&str>) -> String {
@@ -118,42 +331,134 @@ fn format_message(template: &str, player: &str, message: Option<&str>) -> String
     output
 }
 
-fn format_mc_message(template: &str, user: &str, message: &str) -> String {
-    template
-        .replace("{USER}", user)
-        .replace("{MESSAGE}", message)
-}
-
 struct DiscordHandler {
     channel_id: ChannelId,
     server: Arc<Server>,
     to_mc_format: String,
+    to_mc_max_length: usize,
+    state: StateSlot,
+    guild: GuildSlot,
 }
 
 #[async_trait]
 impl EventHandler for DiscordHandler {
-    async fn message(&self, _ctx: Context, msg: Message) {
+    /// Slash commands are registered on the bridge channel's guild so they show up immediately.
+    async fn ready(&self, ctx: Context, _ready: Ready) {
+        let guild_id = match self.channel_id.to_channel(&ctx).await {
+            Ok(Channel::Guild(channel)) => channel.guild_id,
+            Ok(_) => {
+                log::warn!("Discord bridge channel is not in a guild; slash commands disabled");
+                return;
+            }
+            Err(err) => {
+                log::warn!("Failed to look up the Discord bridge channel: {err}");
+                return;
+            }
+        };
+        let _ = self.guild.set(guild_id);
+        if let Err(err) = guild_id.set_commands(&ctx.http, command_definitions()).await {
+            log::warn!("Failed to register Discord slash commands: {err}");
+        }
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let Interaction::Command(command) = interaction else {
+            return;
+        };
+        let Some(state) = self.state.get() else {
+            return;
+        };
+        handle_command(&ctx, &command, &self.server, state).await;
+    }
+
+    async fn message(&self, ctx: Context, msg: Message) {
         if msg.author.bot {
             return;
         }
-        if msg.channel_id != self.channel_id {
+        let handled = match self.state.get() {
+            Some(state) => handle_console_message(&ctx, &msg, &self.server, state).await,
+            None => false,
+        };
+        if handled {
             return;
         }
-        let content = msg.content.trim();
-        if content.is_empty() {
+        let Some(inbound) = self.inbound(msg.channel_id) else {
             return;
-        }
+        };
+
+        let Some(text) = render_message(
+            &ctx,
+            &msg,
+            self.state.get().map(Arc::as_ref),
+            &inbound.format,
+            inbound.max_length,
+        )
+        .await
+        else {
+            return;
+        };
+        broadcast_system_message(&self.server, text, inbound.permission.as_deref()).await;
+    }
+}
+
+/// How messages from a relayed Discord channel are shown in-game.
+struct Inbound {
+    format: String,
+    max_length: usize,
+    permission: Option<String>,
+}
 
-        let formatted = format_mc_message(&self.to_mc_format, &msg.author.name, content);
-        let text = TextComponent::text(formatted);
-        broadcast_system_message(&self.server, text).await;
+impl DiscordHandler {
+    /// Settings for a channel relayed in-game, or `None` if the channel is not relayed.
+    /// The bridge channel uses `discord_to_mc_format` unless it is listed explicitly.
+    fn inbound(&self, channel: ChannelId) -> Option<Inbound> {
+        let Some(state) = self.state.get() else {
+            return (channel == self.channel_id).then(|| Inbound {
+                format: self.to_mc_format.clone(),
+                max_length: self.to_mc_max_length,
+                permission: None,
+            });
+        };
+        let config = state.config.read().unwrap();
+        let listed = config
+            .discord_inbound_channels
+            .iter()
+            .find(|entry| entry.channel_id == channel.get());
+        match listed {
+            Some(entry) => Some(Inbound {
+                format: if entry.format.trim().is_empty() {
+                    config.discord_to_mc_format.clone()
+                } else {
+                    entry.format.clone()
+                },
+                max_length: config.discord_to_mc_max_length,
+                permission: Some(entry.permission.trim().to_string())
+                    .filter(|permission| !permission.is_empty()),
+            }),
+            None if channel == self.channel_id => Some(Inbound {
+                format: config.discord_to_mc_format.clone(),
+                max_length: config.discord_to_mc_max_length,
+                permission: None,
+            }),
+            None => None,
+        }
     }
 }
 
-async fn broadcast_system_message(server: &Server, message: TextComponent) {
+async fn broadcast_system_message(
+    server: &Server,
+    message: TextComponent,
+    permission: Option<&str>,
+) {
     for world in server.worlds.read().await.iter() {
         for player in world.players.read().await.values() {
-            player.send_system_message(&message).await;
+            let allowed = match permission {
+                Some(permission) => player.has_permission(permission).await,
+                None => true,
+            };
+            if allowed {
+                player.send_system_message(&message).await;
+            }
         }
     }
 }
@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use serenity::model::application::{CommandInteraction, CommandOptionType};
+use serenity::prelude::Context;
+
+use pumpkin::server::Server;
+
+use crate::PluginState;
+use crate::links::{links_path, save_links, complete_link};
+use crate::commands::{HealthSnapshot, current_playtime_secs, tps_summary};
+use crate::seen::{find_by_name, format_duration};
+use crate::vanish::is_vanished;
+
+const OPTION_PLAYER: &str = "player";
+const OPTION_CODE: &str = "code";
+const EMBED_COLOR: u32 = 0x5865F2;
+
+pub fn command_definitions() -> Vec<CreateCommand> {
+    let player_option = || {
+        CreateCommandOption::new(CommandOptionType::String, OPTION_PLAYER, "Player name")
+            .required(true)
+    };
+    vec![
+        CreateCommand::new("online").description("List online players."),
+        CreateCommand::new("tps").description("Show server TPS."),
+        CreateCommand::new("playtime")
+            .description("Show a player's playtime.")
+            .add_option(player_option()),
+        CreateCommand::new("seen")
+            .description("Show when a player was last online.")
+            .add_option(player_option()),
+        CreateCommand::new("health").description("Show server health."),
+        CreateCommand::new("link")
+            .description("Link your Minecraft account using the code from /link in-game.")
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, OPTION_CODE, "Link code")
+                    .required(true),
+            ),
+    ]
+}
+
+fn string_option(command: &CommandInteraction, name: &str) -> Option<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_str())
+        .map(|value| value.trim().to_string())
+}
+
+/// Online players that are not vanished; Discord users never see vanished staff.
+async fn visible_names(server: &Server, state: &PluginState) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut seen = HashSet::new();
+    for world in server.worlds.read().await.iter() {
+        for (uuid, player) in world.players.read().await.iter() {
+            if seen.insert(*uuid) && !is_vanished(&state.vanished, *uuid) {
+                names.push(player.gameprofile.name.clone());
+            }
+        }
+    }
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+fn online_embed(server: &Server, names: &[String]) -> CreateEmbed {
+    let max = server.basic_config.max_players;
+    let max_display = if max == 0 {
+        "inf".to_string()
+    } else {
+        max.to_string()
+    };
+    let list = if names.is_empty() {
+        "Nobody is online.".to_string()
+    } else {
+        names.join(", ")
+    };
+    CreateEmbed::new()
+        .title(format!("Online ({}/{max_display})", names.len()))
+        .description(list)
+        .color(EMBED_COLOR)
+}
+
+fn tps_embed(server: &Server) -> CreateEmbed {
+    let embed = CreateEmbed::new().title("TPS").color(EMBED_COLOR);
+    match tps_summary(server) {
+        Some((tps, mspt, target)) => embed
+            .field("TPS", format!("{tps:.2}"), true)
+            .field("MSPT", format!("{mspt:.2}"), true)
+            .field("Target", format!("{target:.1}"), true),
+        None => embed.description("N/A"),
+    }
+}
+
+fn health_embed(server: &Server, state: &PluginState) -> CreateEmbed {
+    let health = HealthSnapshot::collect(server, state);
+    let embed = CreateEmbed::new()
+        .title("Server health")
+        .color(EMBED_COLOR)
+        .field("Ram usage", format!("`{}`", health.mem_bar()), false)
+        .field("Storage usage", format!("`{}`", health.disk_bar()), false)
+        .field("Average TPS", format!("{:.2}", health.tps), true)
+        .field("Average MSPT", format!("{:.2}", health.mspt), true)
+        .field("Uptime", health.uptime.clone(), true)
+        .field("Chat", health.chat_status, true)
+        .field("Slow mode", health.slow_label.clone(), true);
+    match health.discord_queue {
+        Some(queue) => embed.field("Discord queue", queue.label(), false),
+        None => embed,
+    }
+}
+
+async fn seen_text(server: &Server, state: &PluginState, name: &str) -> String {
+    if let Some(player) = server.get_player_by_name(name).await {
+        if !is_vanished(&state.vanished, player.gameprofile.id) {
+            return format!("{} is online.", player.gameprofile.name);
+        }
+    }
+    let seen = state.seen.read().unwrap();
+    match find_by_name(&seen, name) {
+        Some(entry) => {
+            let elapsed = SystemTime::now().duration_since(entry.last_seen).unwrap_or_default();
+            format!("{} was last seen {} ago.", entry.name, format_duration(elapsed))
+        }
+        None => format!("No data for {name}."),
+    }
+}
+
+fn playtime_text(state: &PluginState, name: &str) -> String {
+    let entry = {
+        let seen = state.seen.read().unwrap();
+        find_by_name(&seen, name).map(|entry| (entry.uuid, entry.name.clone()))
+    };
+    match entry {
+        Some((uuid, name)) => {
+            let total = current_playtime_secs(state, uuid);
+            format!("{name}'s playtime: {}", format_duration(Duration::from_secs(total)))
+        }
+        None => format!("No data for {name}."),
+    }
+}
+
+async fn link_text(server: &Server, state: &PluginState, discord_id: u64, code: &str) -> String {
+    let expiry = Duration::from_secs(state.config.read().unwrap().link_code_expiry_seconds);
+    let result = {
+        let mut links = state.links.write().unwrap();
+        match links.redeem(code, discord_id, expiry) {
+            Ok(entry) => {
+                if let Err(err) = save_links(&links_path(&state.data_dir), &links) {
+                    log::warn!("Failed to save links.yml: {err}");
+                }
+                Ok(entry)
+            }
+            Err(err) => Err(err),
+        }
+    };
+    match result {
+        Ok(entry) => {
+            complete_link(server, state, &entry).await;
+            format!("Linked to {}.", entry.name)
+        }
+        Err(err) => err,
+    }
+}
+
+/// Answers a slash command. Lookups about individual players, server health and linking are
+/// only shown to the user who asked.
+pub async fn handle_command(
+    ctx: &Context,
+    command: &CommandInteraction,
+    server: &Server,
+    state: &PluginState,
+) {
+    let response = match command.data.name.as_str() {
+        "online" => {
+            let names = visible_names(server, state).await;
+            CreateInteractionResponseMessage::new().embed(online_embed(server, &names))
+        }
+        "tps" => CreateInteractionResponseMessage::new().embed(tps_embed(server)),
+        "health" => CreateInteractionResponseMessage::new()
+            .embed(health_embed(server, state))
+            .ephemeral(true),
+        "seen" | "playtime" => {
+            let Some(name) = string_option(command, OPTION_PLAYER) else {
+                return;
+            };
+            let text = if command.data.name == "seen" {
+                seen_text(server, state, &name).await
+            } else {
+                playtime_text(state, &name)
+            };
+            CreateInteractionResponseMessage::new()
+                .content(text)
+                .ephemeral(true)
+        }
+        "link" => {
+            let Some(code) = string_option(command, OPTION_CODE) else {
+                return;
+            };
+            let text = link_text(server, state, command.user.id.get(), &code).await;
+            CreateInteractionResponseMessage::new()
+                .content(text)
+                .ephemeral(true)
+        }
+        _ => return,
+    };
+
+    if let Err(err) = command
+        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+        .await
+    {
+        log::warn!("Failed to answer Discord /{}: {err}", command.data.name);
+    }
+}
@@ -0,0 +1,275 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use pumpkin::SHOULD_STOP;
+use pumpkin::command::CommandSender;
+use pumpkin::command::dispatcher::CommandError;
+use pumpkin::server::Server;
+use serenity::model::channel::Message;
+use serenity::prelude::Context;
+use tokio::sync::Mutex;
+
+use crate::PluginState;
+use crate::audit::AuditRecord;
+use crate::commandspy::matches_root;
+use crate::discord_bot::send_discord_channel;
+use crate::mutes::command_root;
+use crate::seen::unix_now;
+
+/// Log lines are collected for this long before being sent, so a burst of output
+/// becomes a few messages instead of one per line.
+const BATCH_INTERVAL: Duration = Duration::from_secs(5);
+/// Upper bound on messages per batch; older lines beyond it are summarised as skipped.
+const MAX_BLOCKS_PER_BATCH: usize = 4;
+/// Discord's 2000 character limit minus the code fence.
+const BLOCK_BODY_LIMIT: usize = 1990;
+
+fn is_authorized(msg: &Message, allowed: &[u64]) -> bool {
+    if allowed.contains(&msg.author.id.get()) {
+        return true;
+    }
+    match &msg.member {
+        Some(member) => member.roles.iter().any(|role| allowed.contains(&role.get())),
+        None => false,
+    }
+}
+
+/// Runs a message from the console channel as a console command and replies with its output.
+/// Returns false when the message was not in the console channel.
+pub async fn handle_console_message(
+    ctx: &Context,
+    msg: &Message,
+    server: &Arc<Server>,
+    state: &PluginState,
+) -> bool {
+    let config = {
+        let guard = state.config.read().unwrap();
+        guard.clone()
+    };
+    if config.discord_console_channel_id == 0
+        || msg.channel_id.get() != config.discord_console_channel_id
+    {
+        return false;
+    }
+    if !is_authorized(msg, &config.discord_console_allowed_ids) {
+        return true;
+    }
+    let command = msg.content.trim().trim_start_matches('/');
+    if command.is_empty() {
+        return true;
+    }
+
+    // The RCON sender has console permissions and collects the output instead of logging it.
+    let output = Mutex::new(Vec::new());
+    let result = {
+        let dispatcher = server.command_dispatcher.read().await;
+        dispatcher
+            .dispatch(&CommandSender::Rcon(&output), server, command)
+            .await
+    };
+    let mut lines = output.into_inner();
+    if let Err(err) = &result {
+        lines.push(match err {
+            CommandError::CommandFailed(text) => text.as_ref().clone().get_text(),
+            _ => "Unknown command or invalid syntax.".to_string(),
+        });
+    }
+
+    state.audit.append(&AuditRecord {
+        time: unix_now(),
+        actor: format!("Discord:{}", msg.author.name),
+        command: "console".to_string(),
+        targets: Vec::new(),
+        details: command.to_string(),
+        world: None,
+        position: None,
+        success: result.is_ok(),
+    });
+
+    let replies = if lines.is_empty() {
+        vec!["Command sent (no output).".to_string()]
+    } else {
+        code_blocks(&lines)
+    };
+    for reply in replies {
+        if let Err(err) = msg.channel_id.say(&ctx.http, reply).await {
+            log::warn!("Failed to send Discord console output: {err}");
+            break;
+        }
+    }
+    true
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        output.push(ch);
+    }
+    output
+}
+
+/// Packs lines into as few code blocks as possible. Backticks are swapped out so a line
+/// cannot close the fence early.
+fn code_blocks(lines: &[String]) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut body = String::new();
+    for line in lines {
+        let mut line = strip_ansi(line).replace('`', "'");
+        if line.len() > BLOCK_BODY_LIMIT {
+            let mut end = BLOCK_BODY_LIMIT;
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            line.truncate(end);
+        }
+        if !body.is_empty() && body.len() + line.len() + 1 > BLOCK_BODY_LIMIT {
+            blocks.push(format!("```\n{body}\n```"));
+            body.clear();
+        }
+        if !body.is_empty() {
+            body.push('\n');
+        }
+        body.push_str(&line);
+    }
+    if !body.is_empty() {
+        blocks.push(format!("```\n{body}\n```"));
+    }
+    blocks
+}
+
+/// Cuts the arguments off commands that `/commandspy` hides, so passwords typed with `/login`
+/// and the like never reach the console channel.
+fn redact_hidden_commands(line: &str, hidden: &[String]) -> String {
+    for (index, _) in line.match_indices('/') {
+        let rest = &line[index + 1..];
+        if !matches_root(hidden, &command_root(rest)) {
+            continue;
+        }
+        let command_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        return format!("{}/{} [hidden]", &line[..index], &rest[..command_end]);
+    }
+    line.to_string()
+}
+
+/// Follows a log file from its current end, starting over when it is rotated or truncated.
+struct LogTail {
+    path: String,
+    offset: Option<u64>,
+    partial: String,
+}
+
+impl LogTail {
+    fn new(path: String) -> Self {
+        Self {
+            path,
+            offset: None,
+            partial: String::new(),
+        }
+    }
+
+    fn read_new_lines(&mut self) -> std::io::Result<Vec<String>> {
+        let mut file = File::open(&self.path)?;
+        let len = file.metadata()?.len();
+        let offset = match self.offset {
+            Some(offset) if offset <= len => offset,
+            Some(_) => 0,
+            None => len,
+        };
+        file.seek(SeekFrom::Start(offset))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        self.offset = Some(offset + bytes.len() as u64);
+
+        self.partial.push_str(&String::from_utf8_lossy(&bytes));
+        let Some(last_newline) = self.partial.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.partial.split_off(last_newline + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        Ok(complete
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// Mirrors new lines of the server log into the console channel.
+pub fn start_console_log_task(state: Arc<PluginState>) {
+    if state.discord_bridge.is_none() {
+        return;
+    }
+    thread::spawn(move || {
+        let mut tail: Option<LogTail> = None;
+        let mut warned = false;
+
+        loop {
+            if SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+            thread::sleep(BATCH_INTERVAL);
+
+            let (channel_id, path, hidden) = {
+                let config = state.config.read().unwrap();
+                (
+                    config.discord_console_channel_id,
+                    config.discord_console_log_file.trim().to_string(),
+                    config.commandspy_hidden_commands.clone(),
+                )
+            };
+            if channel_id == 0 || path.is_empty() {
+                tail = None;
+                continue;
+            }
+            if tail.as_ref().is_none_or(|tail| tail.path != path) {
+                tail = Some(LogTail::new(path.clone()));
+                warned = false;
+            }
+            let Some(current) = tail.as_mut() else {
+                continue;
+            };
+
+            let lines = match current.read_new_lines() {
+                Ok(lines) => lines,
+                Err(err) => {
+                    if !warned {
+                        log::warn!("Failed to read {path} for the Discord console: {err}");
+                        warned = true;
+                    }
+                    continue;
+                }
+            };
+            warned = false;
+
+            let lines = lines
+                .iter()
+                .map(|line| redact_hidden_commands(line, &hidden))
+                .collect::<Vec<_>>();
+            let mut blocks = code_blocks(&lines);
+            if blocks.len() > MAX_BLOCKS_PER_BATCH {
+                let skipped = blocks.len() - MAX_BLOCKS_PER_BATCH;
+                blocks.drain(..skipped);
+                send_discord_channel(
+                    state.discord_bridge.as_ref(),
+                    channel_id,
+                    format!("({skipped} older log messages skipped)"),
+                );
+            }
+            for block in blocks {
+                send_discord_channel(state.discord_bridge.as_ref(), channel_id, block);
+            }
+        }
+    });
+}
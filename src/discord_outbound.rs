@@ -0,0 +1,569 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock, mpsc as std_mpsc};
+use std::time::{Duration, Instant};
+
+use serenity::builder::{
+    CreateAllowedMentions, CreateEmbed, CreateMessage, CreateWebhook, EditChannel,
+    ExecuteWebhook,
+};
+use serenity::gateway::{ActivityData, ShardManager};
+use serenity::http::{Http, HttpError};
+use serenity::model::id::{ChannelId, GuildId, RoleId, UserId};
+use serenity::model::webhook::Webhook;
+use tokio::sync::mpsc;
+
+use pumpkin::command::CommandSender;
+use pumpkin::server::Server;
+use uuid::Uuid;
+
+use crate::PluginState;
+use crate::links::{links_path, save_links};
+
+/// The bot starts before `PluginState` exists; the state is attached once it has been built.
+pub type StateSlot = Arc<OnceLock<Arc<PluginState>>>;
+/// The bridge channel's guild, known once the gateway is ready.
+pub type GuildSlot = Arc<OnceLock<GuildId>>;
+/// The HTTP client of the current gateway client, so the worker shares its rate limiter.
+/// Holds a standalone client until the first connection.
+pub type HttpSlot = Arc<RwLock<Arc<Http>>>;
+/// The shard manager of the current gateway client; replaced on every reconnect.
+pub type ShardSlot = Arc<RwLock<Option<Arc<ShardManager>>>>;
+
+/// Messages waiting for Discord beyond this are dropped and counted.
+pub const QUEUE_CAPACITY: usize = 512;
+/// Discord's message length limit.
+const MESSAGE_LIMIT: usize = 2000;
+const MAX_ATTEMPTS: u32 = 4;
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+/// How long chat posts as the bot after the channel webhook could not be set up, before the
+/// webhook is tried again.
+const WEBHOOK_RETRY_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Name of the webhook the bot creates (or reuses) in a channel for player chat.
+const CHAT_WEBHOOK_NAME: &str = "JinxCore Chat";
+
+pub enum DiscordOutbound {
+    ChannelMessage(ChannelId, String),
+    /// A chat line; lines queued back to back for the same channel are merged.
+    Chat(ChannelId, String),
+    Embed(ChannelId, String, u32),
+    SyncMember(MemberSync),
+    /// Chat sent through a channel webhook as the player; `fallback` is posted by the bot
+    /// if the webhook is unavailable.
+    WebhookChat {
+        channel: ChannelId,
+        username: String,
+        avatar_url: Option<String>,
+        content: String,
+        fallback: String,
+    },
+    /// Bridge channel topic and bot presence; `None` leaves that part unchanged.
+    Status {
+        topic: Option<String>,
+        presence: Option<String>,
+    },
+    /// Acknowledged once everything queued before it has been sent.
+    Flush(std_mpsc::Sender<()>),
+}
+
+/// Role changes for a linked member, plus console commands for roles they gained or lost.
+pub struct MemberSync {
+    pub discord_id: u64,
+    pub uuid: Uuid,
+    /// False once the link is gone, which counts as losing every role.
+    pub linked: bool,
+    /// `(role, should_have)` pairs.
+    pub roles: Vec<(u64, bool)>,
+    pub role_commands: Vec<(u64, String)>,
+    pub role_removed_commands: Vec<(u64, String)>,
+    /// The link's `command_roles` when the sync was queued.
+    pub command_roles: Vec<u64>,
+}
+
+/// Counters shared between the bridge and the outbound worker.
+#[derive(Default)]
+pub struct OutboundStats {
+    dropped: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl OutboundStats {
+    /// Counts a message that did not fit in the queue. Only the first one is logged, the
+    /// total shows up in `/jinx health`.
+    pub fn record_dropped(&self) {
+        if self.dropped.fetch_add(1, Ordering::Relaxed) == 0 {
+            log::warn!("Discord outbound queue is full; dropping messages");
+        }
+    }
+
+    fn record_failed(&self, action: &str, err: serenity::Error) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        log::warn!("Failed to {action} on Discord: {err}");
+    }
+}
+
+/// Outbound queue numbers for `/jinx health`.
+#[derive(Clone, Copy)]
+pub struct QueueStats {
+    pub queued: usize,
+    pub dropped: u64,
+    pub failed: u64,
+}
+
+impl QueueStats {
+    pub fn new(stats: &OutboundStats, queued: usize) -> Self {
+        Self {
+            queued,
+            dropped: stats.dropped.load(Ordering::Relaxed),
+            failed: stats.failed.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.dropped == 0 && self.failed == 0
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{} queued, {} dropped, {} failed",
+            self.queued, self.dropped, self.failed
+        )
+    }
+}
+
+/// Sends queued messages one at a time. The HTTP client outlives its gateway connection, so
+/// sending keeps working while the gateway reconnects.
+pub struct OutboundWorker {
+    http: HttpSlot,
+    channel_id: ChannelId,
+    guild: GuildSlot,
+    shards: ShardSlot,
+    server: Arc<Server>,
+    state: StateSlot,
+    stats: Arc<OutboundStats>,
+    chat_webhooks: HashMap<ChannelId, Webhook>,
+    /// Channels where the webhook could not be set up, and when that was last tried.
+    webhook_unavailable: HashMap<ChannelId, Instant>,
+}
+
+impl OutboundWorker {
+    pub fn new(
+        http: HttpSlot,
+        channel_id: ChannelId,
+        guild: GuildSlot,
+        shards: ShardSlot,
+        server: Arc<Server>,
+        state: StateSlot,
+        stats: Arc<OutboundStats>,
+    ) -> Self {
+        Self {
+            http,
+            channel_id,
+            guild,
+            shards,
+            server,
+            state,
+            stats,
+            chat_webhooks: HashMap::new(),
+            webhook_unavailable: HashMap::new(),
+        }
+    }
+
+    fn http(&self) -> Arc<Http> {
+        self.http.read().unwrap().clone()
+    }
+
+    pub async fn run(mut self, mut rx: mpsc::Receiver<DiscordOutbound>) {
+        let mut pending: Option<DiscordOutbound> = None;
+        loop {
+            let outbound = match pending.take() {
+                Some(outbound) => outbound,
+                None => match rx.recv().await {
+                    Some(outbound) => outbound,
+                    None => break,
+                },
+            };
+            let outbound = merge_backlog(outbound, &mut rx, &mut pending);
+            self.deliver(outbound).await;
+        }
+    }
+
+    async fn deliver(&mut self, outbound: DiscordOutbound) {
+        match outbound {
+            DiscordOutbound::ChannelMessage(channel, content)
+            | DiscordOutbound::Chat(channel, content) => {
+                self.send_message(channel, &content).await;
+            }
+            DiscordOutbound::Embed(channel, description, color) => {
+                let http = &self.http();
+                let description = description.as_str();
+                let sent = with_retry(move || async move {
+                    let embed = CreateEmbed::new().description(description).color(color);
+                    let builder = CreateMessage::new()
+                        .embed(embed)
+                        .allowed_mentions(allowed_mentions());
+                    channel.send_message(http, builder).await
+                })
+                .await;
+                if let Err(err) = sent {
+                    self.stats.record_failed("send an embed", err);
+                }
+            }
+            DiscordOutbound::SyncMember(sync) => {
+                let Some(guild_id) = self.guild.get() else {
+                    return;
+                };
+                let state = self.state.get().map(Arc::as_ref);
+                sync_member(&self.http(), *guild_id, &self.server, state, sync).await;
+            }
+            DiscordOutbound::WebhookChat {
+                channel,
+                username,
+                avatar_url,
+                content,
+                fallback,
+            } => {
+                let avatar_url = avatar_url.as_deref();
+                let sent = self.send_webhook_chat(channel, &username, avatar_url, &content).await;
+                if !sent {
+                    self.send_message(channel, &fallback).await;
+                }
+            }
+            DiscordOutbound::Status { topic, presence } => {
+                if let Some(topic) = topic {
+                    let http = &self.http();
+                    let channel = self.channel_id;
+                    let topic = topic.as_str();
+                    let edited = with_retry(move || async move {
+                        channel.edit(http, EditChannel::new().topic(topic)).await
+                    })
+                    .await;
+                    if let Err(err) = edited {
+                        self.stats.record_failed("update the channel topic", err);
+                    }
+                }
+                if let Some(presence) = presence {
+                    let manager = self.shards.read().unwrap().clone();
+                    if let Some(manager) = manager {
+                        let runners = manager.runners.lock().await;
+                        for runner in runners.values() {
+                            runner
+                                .runner_tx
+                                .set_activity(Some(ActivityData::custom(presence.clone())));
+                        }
+                    }
+                }
+            }
+            DiscordOutbound::Flush(ack) => {
+                let _ = ack.send(());
+            }
+        }
+    }
+
+    async fn send_message(&self, channel: ChannelId, content: &str) {
+        let http = &self.http();
+        let sent = with_retry(move || async move {
+            let builder = CreateMessage::new()
+                .content(content)
+                .allowed_mentions(allowed_mentions());
+            channel.send_message(http, builder).await
+        })
+        .await;
+        if let Err(err) = sent {
+            self.stats.record_failed("send a message", err);
+        }
+    }
+
+    /// Posts chat through the channel's webhook. Returns false if the bot should post the
+    /// fallback line instead.
+    async fn send_webhook_chat(
+        &mut self,
+        channel: ChannelId,
+        username: &str,
+        avatar_url: Option<&str>,
+        content: &str,
+    ) -> bool {
+        let known = self.chat_webhooks.contains_key(&channel);
+        let retry_due = self
+            .webhook_unavailable
+            .get(&channel)
+            .is_none_or(|failed| failed.elapsed() >= WEBHOOK_RETRY_INTERVAL);
+        if !known && retry_due {
+            match find_or_create_webhook(&self.http(), channel).await {
+                Ok(webhook) => {
+                    self.webhook_unavailable.remove(&channel);
+                    self.chat_webhooks.insert(channel, webhook);
+                }
+                Err(err) => {
+                    log::warn!(
+                        "Discord chat webhook unavailable in {channel}, \
+                         posting as the bot instead: {err}"
+                    );
+                    self.webhook_unavailable.insert(channel, Instant::now());
+                }
+            }
+        }
+        let Some(webhook) = self.chat_webhooks.get(&channel) else {
+            return false;
+        };
+        let http = &self.http();
+        let executed = with_retry(move || async move {
+            let mut builder = ExecuteWebhook::new()
+                .content(content)
+                .username(username)
+                .allowed_mentions(allowed_mentions());
+            if let Some(avatar_url) = avatar_url {
+                builder = builder.avatar_url(avatar_url);
+            }
+            webhook.execute(http, false, builder).await
+        })
+        .await;
+        if executed.is_ok() {
+            return true;
+        }
+        // Most likely deleted; look it up again next time.
+        self.chat_webhooks.remove(&channel);
+        false
+    }
+}
+
+/// Folds chat lines that are already waiting behind `first` into it, as long as they go to
+/// the same place and fit in one message. Nothing waits for more lines, so quiet chat is
+/// sent line by line as before.
+fn merge_backlog(
+    mut first: DiscordOutbound,
+    rx: &mut mpsc::Receiver<DiscordOutbound>,
+    pending: &mut Option<DiscordOutbound>,
+) -> DiscordOutbound {
+    if !matches!(first, DiscordOutbound::Chat(..) | DiscordOutbound::WebhookChat { .. }) {
+        return first;
+    }
+    while let Ok(next) = rx.try_recv() {
+        if let Err(next) = merge(&mut first, next) {
+            *pending = Some(next);
+            break;
+        }
+    }
+    first
+}
+
+/// Appends `next` to `target`, or hands it back if the two cannot share a message.
+fn merge(target: &mut DiscordOutbound, next: DiscordOutbound) -> Result<(), DiscordOutbound> {
+    match (target, next) {
+        (DiscordOutbound::Chat(channel, content), DiscordOutbound::Chat(next_channel, line))
+            if *channel == next_channel && fits(content, &line) =>
+        {
+            append_line(content, &line);
+            Ok(())
+        }
+        (
+            DiscordOutbound::WebhookChat {
+                channel,
+                username,
+                avatar_url,
+                content,
+                fallback,
+            },
+            DiscordOutbound::WebhookChat {
+                channel: next_channel,
+                username: next_username,
+                avatar_url: next_avatar_url,
+                content: next_content,
+                fallback: next_fallback,
+            },
+        ) if *channel == next_channel
+            && *username == next_username
+            && *avatar_url == next_avatar_url
+            && fits(content, &next_content)
+            && fits(fallback, &next_fallback) =>
+        {
+            append_line(content, &next_content);
+            append_line(fallback, &next_fallback);
+            Ok(())
+        }
+        (_, next) => Err(next),
+    }
+}
+
+fn fits(content: &str, line: &str) -> bool {
+    content.len() + 1 + line.len() <= MESSAGE_LIMIT
+}
+
+fn append_line(content: &mut String, line: &str) {
+    content.push('\n');
+    content.push_str(line);
+}
+
+/// Runs a request until it succeeds, backing off between attempts. Client errors are returned
+/// right away since repeating them cannot help. That includes 429s: the client's rate limiter
+/// already waits for the response's retry-after and repeats the request itself, so one that
+/// gets here came without a usable retry-after.
+async fn with_retry<F, Fut, T>(mut request: F) -> serenity::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = serenity::Result<T>>,
+{
+    let mut delay = RETRY_BASE_DELAY;
+    let mut attempt = 1;
+    loop {
+        let err = match request().await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        let retryable = match status_code(&err) {
+            Some(code) => code >= 500,
+            None => true,
+        };
+        if !retryable || attempt >= MAX_ATTEMPTS {
+            return Err(err);
+        }
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
+fn status_code(err: &serenity::Error) -> Option<u16> {
+    match err {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+            Some(response.status_code.as_u16())
+        }
+        _ => None,
+    }
+}
+
+/// Pings for users still work; `@everyone`, `@here` and roles never do.
+fn allowed_mentions() -> CreateAllowedMentions {
+    CreateAllowedMentions::new().all_users(true)
+}
+
+async fn find_or_create_webhook(
+    http: &Arc<Http>,
+    channel_id: ChannelId,
+) -> serenity::Result<Webhook> {
+    let existing = channel_id
+        .webhooks(http)
+        .await?
+        .into_iter()
+        .find(|webhook| {
+            webhook.name.as_deref() == Some(CHAT_WEBHOOK_NAME) && webhook.token.is_some()
+        });
+    match existing {
+        Some(webhook) => Ok(webhook),
+        None => {
+            channel_id
+                .create_webhook(http, CreateWebhook::new(CHAT_WEBHOOK_NAME))
+                .await
+        }
+    }
+}
+
+async fn sync_member(
+    http: &Arc<Http>,
+    guild_id: GuildId,
+    server: &Arc<Server>,
+    state: Option<&PluginState>,
+    sync: MemberSync,
+) {
+    let member = match guild_id.member(http, UserId::new(sync.discord_id)).await {
+        Ok(member) => Some(member),
+        Err(err) => {
+            log::warn!("Failed to look up linked Discord member {}: {err}", sync.discord_id);
+            None
+        }
+    };
+    if let Some(member) = &member {
+        for (role, wanted) in &sync.roles {
+            let role = RoleId::new(*role);
+            let has = member.roles.contains(&role);
+            let result = if *wanted && !has {
+                member.add_role(http, role).await
+            } else if !wanted && has {
+                member.remove_role(http, role).await
+            } else {
+                continue;
+            };
+            if let Err(err) = result {
+                log::warn!("Failed to update Discord role {role} for {}: {err}", sync.discord_id);
+            }
+        }
+    }
+    // Without the member their roles are unknown, so role commands wait for the next sync.
+    if sync.linked && member.is_none() {
+        return;
+    }
+
+    let holds = |role: u64| {
+        if !sync.linked {
+            return false;
+        }
+        match sync.roles.iter().find(|(synced, _)| *synced == role) {
+            Some((_, wanted)) => *wanted,
+            None => member
+                .as_ref()
+                .is_some_and(|member| member.roles.contains(&RoleId::new(role))),
+        }
+    };
+    let applied = state
+        .and_then(|state| {
+            let links = state.links.read().unwrap();
+            links.by_uuid(sync.uuid).map(|entry| entry.command_roles.clone())
+        })
+        .unwrap_or_else(|| sync.command_roles.clone());
+
+    let mut tracked = sync
+        .role_commands
+        .iter()
+        .chain(&sync.role_removed_commands)
+        .map(|(role, _)| *role)
+        .collect::<Vec<_>>();
+    tracked.sort_unstable();
+    tracked.dedup();
+
+    let mut now_applied = Vec::new();
+    let mut commands = Vec::new();
+    for role in tracked {
+        let now = holds(role);
+        let was = applied.contains(&role);
+        if now {
+            now_applied.push(role);
+        }
+        let list = if now && !was {
+            &sync.role_commands
+        } else if !now && was {
+            &sync.role_removed_commands
+        } else {
+            continue;
+        };
+        commands.extend(
+            list.iter()
+                .filter(|(listed, _)| *listed == role)
+                .map(|(_, command)| command.clone()),
+        );
+    }
+
+    if let Some(state) = state.filter(|_| sync.linked) {
+        let mut links = state.links.write().unwrap();
+        let changed = match links.links.iter_mut().find(|entry| entry.uuid == sync.uuid) {
+            Some(entry) if entry.command_roles != now_applied => {
+                entry.command_roles = now_applied;
+                true
+            }
+            _ => false,
+        };
+        if changed {
+            if let Err(err) = save_links(&links_path(&state.data_dir), &links) {
+                log::warn!("Failed to save links.yml: {err}");
+            }
+        }
+    }
+
+    let dispatcher = server.command_dispatcher.read().await;
+    for command in commands {
+        dispatcher
+            .handle_command(&CommandSender::Console, server, &command)
+            .await;
+    }
+}
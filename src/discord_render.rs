@@ -0,0 +1,314 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use pumpkin_util::text::click::ClickEvent;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use serenity::model::channel::{Channel, Message};
+use serenity::model::guild::Role;
+use serenity::model::id::{ChannelId, RoleId};
+use serenity::model::user::User;
+use serenity::prelude::Context;
+
+use crate::PluginState;
+
+const ELLIPSIS: &str = "...";
+
+/// The name shown in Minecraft for a Discord user: the linked Minecraft name if there is one,
+/// then the server nickname, then the display name.
+pub fn display_name(state: Option<&PluginState>, user: &User, nick: Option<&str>) -> String {
+    if let Some(state) = state {
+        let links = state.links.read().unwrap();
+        if let Some(entry) = links.by_discord(user.id.get()) {
+            return entry.name.clone();
+        }
+    }
+    nick.or(user.global_name.as_deref())
+        .unwrap_or(&user.name)
+        .to_string()
+}
+
+/// Replaces `<@user>`, `<@&role>`, `<#channel>` and custom emoji markup with readable text.
+/// Roles and channels are only fetched when the message actually references them.
+async fn resolve_mentions(ctx: &Context, msg: &Message, state: Option<&PluginState>) -> String {
+    let mut output = String::with_capacity(msg.content.len());
+    let mut roles: Option<HashMap<RoleId, Role>> = None;
+    let mut rest = msg.content.as_str();
+
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let token = &rest[start + 1..start + len];
+        let raw = &rest[start..=start + len];
+        rest = &rest[start + len + 1..];
+
+        let resolved = if let Some(id) = token.strip_prefix("@&") {
+            let Ok(id) = id.parse::<u64>() else {
+                output.push_str(raw);
+                continue;
+            };
+            if roles.is_none() {
+                roles = match msg.guild_id {
+                    Some(guild_id) => guild_id.roles(&ctx.http).await.ok(),
+                    None => None,
+                };
+            }
+            roles
+                .as_ref()
+                .and_then(|roles| roles.get(&RoleId::new(id)))
+                .map(|role| format!("@{}", role.name))
+        } else if let Some(id) = token.strip_prefix('@') {
+            let id = id.trim_start_matches('!');
+            msg.mentions
+                .iter()
+                .find(|user| user.id.to_string() == id)
+                .map(|user| {
+                    let nick = user.member.as_ref().and_then(|member| member.nick.as_deref());
+                    format!("@{}", display_name(state, user, nick))
+                })
+        } else if let Some(id) = token.strip_prefix('#') {
+            match id.parse::<u64>() {
+                Ok(id) => match ChannelId::new(id).to_channel(&ctx.http).await {
+                    Ok(Channel::Guild(channel)) => Some(format!("#{}", channel.name)),
+                    _ => None,
+                },
+                Err(_) => None,
+            }
+        } else {
+            // Custom emoji: <:name:id> or <a:name:id>.
+            let emoji = token.strip_prefix("a:").or_else(|| token.strip_prefix(':'));
+            emoji
+                .and_then(|emoji| emoji.split_once(':'))
+                .map(|(name, _)| format!(":{name}:"))
+        };
+        output.push_str(resolved.as_deref().unwrap_or(raw));
+    }
+    output.push_str(rest);
+    output
+}
+
+#[derive(Clone, Copy, Default)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Flag {
+    Bold,
+    Italic,
+    Underline,
+    Strike,
+}
+
+impl Style {
+    fn flag_mut(&mut self, flag: Flag) -> &mut bool {
+        match flag {
+            Flag::Bold => &mut self.bold,
+            Flag::Italic => &mut self.italic,
+            Flag::Underline => &mut self.underline,
+            Flag::Strike => &mut self.strike,
+        }
+    }
+}
+
+struct Span {
+    text: String,
+    style: Style,
+}
+
+fn push_span(spans: &mut Vec<Span>, text: &mut String, style: Style) {
+    if !text.is_empty() {
+        spans.push(Span {
+            text: std::mem::take(text),
+            style,
+        });
+    }
+}
+
+fn is_word_char(ch: Option<&char>) -> bool {
+    ch.is_some_and(|ch| ch.is_alphanumeric())
+}
+
+/// Splits Discord markdown into styled spans. A marker only opens when it is closed later in
+/// the message, so a lone `*` or an underscore inside a word stays literal, like in Discord.
+fn parse_markdown(input: &str) -> Vec<Span> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let mut style = Style::default();
+    let mut i = 0;
+
+    let closes_later = |from: usize, marker: &[char]| {
+        (from..chars.len().saturating_sub(marker.len() - 1))
+            .any(|j| chars[j..].starts_with(marker))
+    };
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '\\' && chars.get(i + 1).is_some_and(|next| next.is_ascii_punctuation()) {
+            text.push(chars[i + 1]);
+            i += 2;
+            continue;
+        }
+        if ch == '`' {
+            let fence = if chars[i..].starts_with(&['`', '`', '`']) { 3 } else { 1 };
+            let marker = &chars[i..i + fence];
+            let close = (i + fence..chars.len().saturating_sub(fence - 1))
+                .find(|&j| chars[j..].starts_with(marker));
+            if let Some(close) = close {
+                let raw: String = chars[i + fence..close].iter().collect();
+                // Drop the language tag of a fenced block.
+                let code = match raw.split_once('\n') {
+                    Some((first, body)) if fence == 3 && !first.contains(' ') => body,
+                    _ => raw.as_str(),
+                };
+                text.push_str(code.trim_matches('\n'));
+                i = close + fence;
+                continue;
+            }
+        }
+
+        let marker = if chars[i..].starts_with(&['*', '*']) {
+            Some((2, Flag::Bold))
+        } else if chars[i..].starts_with(&['_', '_']) {
+            Some((2, Flag::Underline))
+        } else if chars[i..].starts_with(&['~', '~']) {
+            Some((2, Flag::Strike))
+        } else if ch == '*' || ch == '_' {
+            Some((1, Flag::Italic))
+        } else {
+            None
+        };
+
+        if let Some((len, flag)) = marker {
+            let marker = &chars[i..i + len];
+            let mut next = style;
+            let active = *next.flag_mut(flag);
+            let allowed = if ch == '_' && len == 1 {
+                // Intra-word underscores are literal.
+                if active {
+                    !is_word_char(chars.get(i + 1))
+                } else {
+                    i == 0 || !is_word_char(chars.get(i - 1))
+                }
+            } else if active {
+                true
+            } else {
+                !chars.get(i + len).is_none_or(|next| next.is_whitespace())
+            };
+            if allowed && (active || closes_later(i + len, marker)) {
+                push_span(&mut spans, &mut text, style);
+                *next.flag_mut(flag) = !active;
+                style = next;
+                i += len;
+                continue;
+            }
+        }
+
+        text.push(ch);
+        i += 1;
+    }
+    push_span(&mut spans, &mut text, style);
+    spans
+}
+
+/// Cuts spans down to `max_chars` characters (0 = unlimited). Returns true if anything was cut.
+fn truncate_spans(spans: &mut Vec<Span>, max_chars: usize) -> bool {
+    if max_chars == 0 {
+        return false;
+    }
+    let mut remaining = max_chars;
+    for index in 0..spans.len() {
+        let count = spans[index].text.chars().count();
+        if count <= remaining {
+            remaining -= count;
+            continue;
+        }
+        let cut: String = spans[index].text.chars().take(remaining).collect();
+        spans[index].text = cut;
+        spans.truncate(index + 1);
+        return true;
+    }
+    false
+}
+
+fn styled(span: Span) -> TextComponent {
+    let mut component = TextComponent::text(span.text);
+    if span.style.bold {
+        component = component.bold();
+    }
+    if span.style.italic {
+        component = component.italic();
+    }
+    if span.style.underline {
+        component = component.underlined();
+    }
+    if span.style.strike {
+        component = component.strikethrough();
+    }
+    component
+}
+
+/// Builds the in-game line for a Discord message, or `None` if there is nothing to show.
+pub async fn render_message(
+    ctx: &Context,
+    msg: &Message,
+    state: Option<&PluginState>,
+    template: &str,
+    max_length: usize,
+) -> Option<TextComponent> {
+    let content = resolve_mentions(ctx, msg, state).await;
+    let content = content.trim();
+    if content.is_empty() && msg.attachments.is_empty() {
+        return None;
+    }
+
+    let nick = msg.member.as_ref().and_then(|member| member.nick.as_deref());
+    let user = display_name(state, &msg.author, nick);
+    let (before, after) = template.split_once("{MESSAGE}").unwrap_or((template, ""));
+
+    let mut line = TextComponent::text(before.replace("{USER}", &user));
+    if let Some(reply) = &msg.referenced_message {
+        let nick = reply.member.as_ref().and_then(|member| member.nick.as_deref());
+        let name = display_name(state, &reply.author, nick);
+        line = line.add_child(
+            TextComponent::text(format!("(reply to {name}) ")).color_named(NamedColor::Gray),
+        );
+    }
+
+    let mut spans = parse_markdown(content);
+    let truncated = truncate_spans(&mut spans, max_length);
+    for span in spans {
+        line = line.add_child(styled(span));
+    }
+    if truncated {
+        line = line.add_child(TextComponent::text(ELLIPSIS));
+    }
+
+    for attachment in &msg.attachments {
+        let is_image = attachment
+            .content_type
+            .as_deref()
+            .is_some_and(|kind| kind.starts_with("image/"));
+        let label = if is_image { "[image]" } else { "[file]" };
+        line = line.add_child(TextComponent::text(" "));
+        line = line.add_child(
+            TextComponent::text(label)
+                .color_named(NamedColor::Aqua)
+                .underlined()
+                .click_event(ClickEvent::OpenUrl {
+                    url: Cow::Owned(attachment.url.clone()),
+                }),
+        );
+    }
+
+    if !after.is_empty() {
+        line = line.add_child(TextComponent::text(after.replace("{USER}", &user)));
+    }
+    Some(line)
+}
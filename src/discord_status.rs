@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use pumpkin::SHOULD_STOP;
+use pumpkin::server::Server;
+
+use crate::PluginState;
+use crate::config::Config;
+use crate::discord_bot::send_discord_status;
+use crate::scoreboard::Placeholders;
+
+/// Discord allows two topic edits per channel every ten minutes.
+const MIN_INTERVAL: Duration = Duration::from_secs(300);
+const STOP_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Queues the offline topic. Sent on unload, after this task has stopped updating the topic.
+pub fn send_offline_topic(state: &PluginState, config: &Config) {
+    let offline = config.discord_offline_topic.trim();
+    if config.discord_status_enabled && !offline.is_empty() {
+        send_discord_status(state.discord_bridge.as_ref(), Some(offline.to_string()), None);
+    }
+}
+
+/// Keeps the bridge channel topic and the bot presence in sync with the server.
+pub fn start_discord_status_task(server: Arc<Server>, state: Arc<PluginState>) {
+    if state.discord_bridge.is_none() {
+        return;
+    }
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().expect("discord status runtime");
+        let mut last_update: Option<Instant> = None;
+        let mut last_topic = String::new();
+        let mut last_presence = String::new();
+
+        loop {
+            let config = {
+                let guard = state.config.read().unwrap();
+                guard.clone()
+            };
+
+            if SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+
+            let interval = Duration::from_secs(config.discord_status_interval_seconds)
+                .max(MIN_INTERVAL);
+            let due = last_update.is_none_or(|last| last.elapsed() >= interval);
+            if config.discord_status_enabled && due {
+                last_update = Some(Instant::now());
+                let placeholders = runtime.block_on(Placeholders::collect(&server, &state));
+                let topic = placeholders.apply(&config.discord_topic_format);
+                let presence = placeholders.apply(&config.discord_presence_format);
+
+                // Unchanged values are skipped so idle servers do not use up the rate limit.
+                let topic = if topic.trim().is_empty() || topic == last_topic {
+                    None
+                } else {
+                    last_topic = topic.clone();
+                    Some(topic)
+                };
+                let presence = if presence.trim().is_empty() || presence == last_presence {
+                    None
+                } else {
+                    last_presence = presence.clone();
+                    Some(presence)
+                };
+                if topic.is_some() || presence.is_some() {
+                    send_discord_status(state.discord_bridge.as_ref(), topic, presence);
+                }
+            }
+
+            thread::sleep(STOP_CHECK_INTERVAL);
+        }
+    });
+}
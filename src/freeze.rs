@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use pumpkin::SHOULD_STOP;
+use pumpkin::entity::player::{Player, TitleMode};
+use pumpkin::plugin::{BoxFuture, Cancellable, EventHandler};
+use pumpkin::plugin::events::player::player_command_send::PlayerCommandSendEvent;
+use pumpkin::plugin::events::player::player_leave::PlayerLeaveEvent;
+use pumpkin::plugin::events::player::player_move::PlayerMoveEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use uuid::Uuid;
+
+use crate::config::{Config, FreezeNotice};
+use crate::mutes::command_root;
+use crate::staff::notify_staff;
+use crate::{PluginState, branding};
+
+pub const PERMISSION_FREEZE_ALERT: &str = "JinxCore:freeze.alert";
+
+const NOTICE_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug)]
+pub struct FrozenEntry {
+    pub name: String,
+    pub issuer: String,
+}
+
+/// Frozen players by UUID. Kept in memory only, so a frozen player stays frozen across relogs
+/// until staff unfreeze them or the server restarts.
+pub type FrozenMap = Arc<RwLock<HashMap<Uuid, FrozenEntry>>>;
+
+pub fn is_frozen(frozen: &FrozenMap, uuid: Uuid) -> bool {
+    frozen.read().unwrap().contains_key(&uuid)
+}
+
+pub async fn send_freeze_notice(player: &Player, config: &Config) {
+    let message = TextComponent::text(config.freeze_message.clone()).color_named(NamedColor::Red);
+    match config.freeze_notice {
+        FreezeNotice::Title => {
+            player.show_title(&message, &TitleMode::SubTitle).await;
+            let title = TextComponent::text("Frozen").color_named(NamedColor::Red);
+            player.show_title(&title, &TitleMode::Title).await;
+        }
+        FreezeNotice::ActionBar => {
+            player.show_title(&message, &TitleMode::ActionBar).await;
+        }
+    }
+}
+
+/// Takes the freeze notice off screen in whichever spot `send_freeze_notice` put it.
+pub async fn clear_freeze_notice(player: &Player, config: &Config) {
+    let empty = TextComponent::text("");
+    match config.freeze_notice {
+        FreezeNotice::Title => {
+            player.show_title(&empty, &TitleMode::SubTitle).await;
+            player.show_title(&empty, &TitleMode::Title).await;
+        }
+        FreezeNotice::ActionBar => {
+            player.show_title(&empty, &TitleMode::ActionBar).await;
+        }
+    }
+}
+
+/// Re-sends the freeze notice so it stays on screen for as long as the player is frozen.
+pub fn start_freeze_notice_task(server: Arc<Server>, state: Arc<PluginState>) {
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().expect("freeze runtime");
+
+        loop {
+            if SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+
+            let frozen = state.frozen.read().unwrap().keys().copied().collect::<Vec<_>>();
+            if !frozen.is_empty() {
+                let config = {
+                    let guard = state.config.read().unwrap();
+                    guard.clone()
+                };
+                runtime.block_on(async {
+                    for uuid in frozen {
+                        if let Some(player) = server.get_player_by_uuid(uuid).await {
+                            send_freeze_notice(&player, &config).await;
+                        }
+                    }
+                });
+            }
+
+            thread::sleep(NOTICE_INTERVAL);
+        }
+    });
+}
+
+pub struct FreezeMoveHandler {
+    frozen: FrozenMap,
+}
+
+impl FreezeMoveHandler {
+    pub fn new(frozen: FrozenMap) -> Self {
+        Self { frozen }
+    }
+}
+
+impl EventHandler<PlayerMoveEvent> for FreezeMoveHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerMoveEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if !is_frozen(&self.frozen, event.player.gameprofile.id) {
+                return;
+            }
+            // Looking around is fine; only a change of position is rolled back.
+            let (from, to) = (event.from, event.to);
+            if from.x != to.x || from.y != to.y || from.z != to.z {
+                event.set_cancelled(true);
+            }
+        })
+    }
+}
+
+pub struct FreezeCommandHandler {
+    config: Arc<RwLock<Config>>,
+    frozen: FrozenMap,
+}
+
+impl FreezeCommandHandler {
+    pub fn new(config: Arc<RwLock<Config>>, frozen: FrozenMap) -> Self {
+        Self { config, frozen }
+    }
+}
+
+impl EventHandler<PlayerCommandSendEvent> for FreezeCommandHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerCommandSendEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if !is_frozen(&self.frozen, event.player.gameprofile.id) {
+                return;
+            }
+
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+            let root = command_root(&event.command);
+            if config
+                .freeze_allowed_commands
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(&root))
+            {
+                return;
+            }
+
+            event.set_cancelled(true);
+            let msg = branding::brand(
+                TextComponent::text("You cannot use that command while frozen.")
+                    .color_named(NamedColor::Red),
+            );
+            event.player.send_system_message(&msg).await;
+        })
+    }
+}
+
+pub struct FreezeLeaveHandler {
+    frozen: FrozenMap,
+}
+
+impl FreezeLeaveHandler {
+    pub fn new(frozen: FrozenMap) -> Self {
+        Self { frozen }
+    }
+}
+
+impl EventHandler<PlayerLeaveEvent> for FreezeLeaveHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        server: &'a Arc<Server>,
+        event: &'a mut PlayerLeaveEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let Some(entry) = self
+                .frozen
+                .read()
+                .unwrap()
+                .get(&event.player.gameprofile.id)
+                .cloned()
+            else {
+                return;
+            };
+
+            let message = branding::brand(
+                TextComponent::text(format!(
+                    "{} disconnected while frozen (frozen by {}).",
+                    entry.name, entry.issuer
+                ))
+                .color_named(NamedColor::Red),
+            );
+            notify_staff(server, PERMISSION_FREEZE_ALERT, &message).await;
+        })
+    }
+}
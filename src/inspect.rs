@@ -0,0 +1,349 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin::entity::player::Player;
+use pumpkin_inventory::generic_container_screen_handler::GenericContainerScreenHandler;
+use pumpkin_inventory::player::player_inventory::PlayerInventory;
+use pumpkin_data::item::Item;
+use pumpkin_inventory::screen_handler::{
+    InventoryPlayer, ScreenHandler, ScreenHandlerBehaviour, ScreenHandlerFactory,
+};
+use pumpkin_protocol::java::server::play::SlotActionType;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::inventory::{Clearable, Inventory};
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+
+use crate::audit::{AuditLog, AuditRecord};
+use crate::seen::unix_now;
+
+pub const PERMISSION_INVSEE_EDIT: &str = "JinxCore:invsee.edit";
+
+/// `/invsee` shows the 36 main slots followed by the target's armor and offhand slots.
+const INVSEE_ROWS: usize = 6;
+const ENDERSEE_ROWS: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InspectKind {
+    Inventory,
+    EnderChest,
+}
+
+impl InspectKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Inventory => "invsee",
+            Self::EnderChest => "endersee",
+        }
+    }
+
+    fn rows(self) -> usize {
+        match self {
+            Self::Inventory => INVSEE_ROWS,
+            Self::EnderChest => ENDERSEE_ROWS,
+        }
+    }
+}
+
+fn describe(stack: &ItemStack) -> String {
+    if stack.is_empty() {
+        "empty".to_string()
+    } else {
+        format!("{}x minecraft:{}", stack.item_count, stack.item.registry_key)
+    }
+}
+
+/// Fills the slots past the target's own inventory. A full stack takes no merges and clicks on
+/// these slots are refused, so nothing can be put there and lost.
+fn filler() -> ItemStack {
+    ItemStack::new(64, &Item::GRAY_STAINED_GLASS_PANE)
+}
+
+/// A view onto another player's inventory. The screen works on copies of the target's slots;
+/// edits reach the target only through `commit`, which records each one. Without edit rights
+/// every click is refused, so the copies never change.
+pub struct InspectedInventory {
+    target: Arc<dyn Inventory>,
+    size: usize,
+    editable: bool,
+    slots: Vec<Arc<Mutex<ItemStack>>>,
+    /// The target's stacks as of the last sync, to tell the viewer's edits apart.
+    synced: Mutex<Vec<ItemStack>>,
+    audit: AuditLog,
+    template: AuditRecord,
+}
+
+impl fmt::Debug for InspectedInventory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InspectedInventory")
+            .field("size", &self.size)
+            .field("editable", &self.editable)
+            .finish()
+    }
+}
+
+impl InspectedInventory {
+    fn new(
+        target: Arc<dyn Inventory>,
+        size: usize,
+        editable: bool,
+        audit: AuditLog,
+        template: AuditRecord,
+    ) -> Self {
+        let own = target.size();
+        let slots = (0..size)
+            .map(|slot| {
+                let stack = if slot < own { ItemStack::EMPTY.clone() } else { filler() };
+                Arc::new(Mutex::new(stack))
+            })
+            .collect();
+        Self {
+            target,
+            size,
+            editable,
+            slots,
+            synced: Mutex::new(vec![ItemStack::EMPTY.clone(); own]),
+            audit,
+            template,
+        }
+    }
+
+    fn in_target(&self, slot: usize) -> bool {
+        slot < self.target.size()
+    }
+
+    /// Whether a click may go ahead. Clicks outside the window and on the viewer's own
+    /// inventory are fine; anything touching padding, and everything when read-only, is not.
+    fn allows(&self, slot_index: i32, action: &SlotActionType) -> bool {
+        if !self.editable || matches!(action, SlotActionType::PickupAll) {
+            return false;
+        }
+        match usize::try_from(slot_index) {
+            Ok(slot) => slot >= self.size || self.in_target(slot),
+            Err(_) => true,
+        }
+    }
+
+    /// Copies the target's current stack into the view unless the viewer has an unsaved edit
+    /// there. A view the click code is holding is left alone.
+    async fn refresh(&self, slot: usize) {
+        let mut synced = self.synced.lock().await;
+        let Ok(mut view) = self.slots[slot].try_lock() else {
+            return;
+        };
+        if self.editable && !view.are_equal(&synced[slot]) {
+            return;
+        }
+        let current = self.target.get_stack(slot).await.lock().await.clone();
+        *view = current.clone();
+        synced[slot] = current;
+    }
+
+    /// Writes every slot the viewer changed to the target, one audit record per slot.
+    async fn commit(&self) {
+        if !self.editable {
+            return;
+        }
+        let mut synced = self.synced.lock().await;
+        let mut changes = Vec::new();
+        for slot in 0..self.target.size() {
+            let after = {
+                let view = self.slots[slot].lock().await;
+                if view.are_equal(&synced[slot]) {
+                    continue;
+                }
+                view.clone()
+            };
+            let before = self.target.get_stack(slot).await.lock().await.clone();
+            changes.push(format!("slot {slot}: {} -> {}", describe(&before), describe(&after)));
+            self.target.set_stack(slot, after.clone()).await;
+            synced[slot] = after;
+        }
+        if changes.is_empty() {
+            return;
+        }
+        self.target.mark_dirty();
+        self.record_edit(&changes);
+    }
+
+    /// Writes one audit record per commit so a drag across many slots is a single entry.
+    fn record_edit(&self, changes: &[String]) {
+        let mut record = self.template.clone();
+        record.time = unix_now();
+        record.details = changes.join("; ");
+        self.audit.append(&record);
+    }
+}
+
+#[async_trait]
+impl Clearable for InspectedInventory {
+    async fn clear(&self) {}
+}
+
+#[async_trait]
+impl Inventory for InspectedInventory {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    async fn is_empty(&self) -> bool {
+        self.target.is_empty().await
+    }
+
+    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+        if self.in_target(slot) {
+            self.refresh(slot).await;
+        }
+        self.slots[slot].clone()
+    }
+
+    async fn remove_stack(&self, slot: usize) -> ItemStack {
+        if !self.editable || !self.in_target(slot) {
+            return ItemStack::EMPTY.clone();
+        }
+        let mut view = self.slots[slot].lock().await;
+        std::mem::replace(&mut *view, ItemStack::EMPTY.clone())
+    }
+
+    async fn remove_stack_specific(&self, slot: usize, amount: u8) -> ItemStack {
+        if !self.editable || !self.in_target(slot) {
+            return ItemStack::EMPTY.clone();
+        }
+        self.slots[slot].lock().await.split(amount)
+    }
+
+    async fn set_stack(&self, slot: usize, stack: ItemStack) {
+        if !self.editable || !self.in_target(slot) {
+            return;
+        }
+        *self.slots[slot].lock().await = stack;
+    }
+
+    fn mark_dirty(&self) {}
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Wraps the container screen so refused clicks never run and accepted ones are committed to
+/// the target as soon as they finish.
+struct InspectScreenHandler {
+    inner: GenericContainerScreenHandler,
+    inventory: Arc<InspectedInventory>,
+}
+
+#[async_trait]
+impl ScreenHandler for InspectScreenHandler {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_behaviour(&self) -> &ScreenHandlerBehaviour {
+        self.inner.get_behaviour()
+    }
+
+    fn get_behaviour_mut(&mut self) -> &mut ScreenHandlerBehaviour {
+        self.inner.get_behaviour_mut()
+    }
+
+    async fn on_closed(&mut self, player: &dyn InventoryPlayer) {
+        self.inventory.commit().await;
+        self.inner.on_closed(player).await;
+    }
+
+    async fn quick_move(&mut self, player: &dyn InventoryPlayer, slot_index: i32) -> ItemStack {
+        self.inner.quick_move(player, slot_index).await
+    }
+
+    async fn on_slot_click(
+        &mut self,
+        slot_index: i32,
+        button: i32,
+        action_type: SlotActionType,
+        player: &dyn InventoryPlayer,
+    ) {
+        if !self.inventory.allows(slot_index, &action_type) {
+            // The client already shows the click; put its view back.
+            self.sync_state().await;
+            return;
+        }
+        self.inner
+            .on_slot_click(slot_index, button, action_type, player)
+            .await;
+        self.inventory.commit().await;
+    }
+}
+
+pub struct InspectScreenFactory {
+    kind: InspectKind,
+    title: TextComponent,
+    inventory: Arc<InspectedInventory>,
+}
+
+impl InspectScreenFactory {
+    pub fn new(
+        kind: InspectKind,
+        target: &Player,
+        editable: bool,
+        audit: AuditLog,
+        template: AuditRecord,
+    ) -> Self {
+        let inventory: Arc<dyn Inventory> = match kind {
+            InspectKind::Inventory => target.inventory().clone(),
+            InspectKind::EnderChest => target.ender_chest_inventory(),
+        };
+        let suffix = match kind {
+            InspectKind::Inventory => "inventory",
+            InspectKind::EnderChest => "ender chest",
+        };
+        Self {
+            kind,
+            title: TextComponent::text(format!("{}'s {suffix}", target.gameprofile.name)),
+            inventory: Arc::new(InspectedInventory::new(
+                inventory,
+                kind.rows() * 9,
+                editable,
+                audit,
+                AuditRecord {
+                    command: format!("{} edit", kind.label()),
+                    ..template
+                },
+            )),
+        }
+    }
+}
+
+impl ScreenHandlerFactory for InspectScreenFactory {
+    fn create_screen_handler(
+        &self,
+        sync_id: u8,
+        player_inventory: &Arc<PlayerInventory>,
+        _player: &dyn InventoryPlayer,
+    ) -> Option<Arc<Mutex<dyn ScreenHandler>>> {
+        let inventory: Arc<dyn Inventory> = self.inventory.clone();
+        let inner = match self.kind {
+            InspectKind::Inventory => GenericContainerScreenHandler::create_generic_9x6(
+                sync_id,
+                player_inventory,
+                inventory,
+            ),
+            InspectKind::EnderChest => GenericContainerScreenHandler::create_generic_9x3(
+                sync_id,
+                player_inventory,
+                inventory,
+            ),
+        };
+        let handler = InspectScreenHandler {
+            inner,
+            inventory: self.inventory.clone(),
+        };
+        Some(Arc::new(Mutex::new(handler)))
+    }
+
+    fn get_display_name(&self) -> TextComponent {
+        self.title.clone()
+    }
+}
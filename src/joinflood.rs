@@ -0,0 +1,194 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use pumpkin::SHOULD_STOP;
+use pumpkin::net::DisconnectReason;
+use pumpkin::plugin::{BoxFuture, Cancellable, EventHandler};
+use pumpkin::plugin::events::player::player_join::PlayerJoinEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use uuid::Uuid;
+
+use crate::discord_bot::{DiscordRoute, escape_markdown, send_discord_message};
+use crate::staff::notify_staff;
+use crate::webhook::send_webhook_embed;
+use crate::{PluginState, branding};
+
+pub const PERMISSION_JOINFLOOD_ALERT: &str = "JinxCore:joinflood.alert";
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+const LIFT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const ALERT_COLOR: u32 = 0xED4245;
+/// How long a kicked player is remembered, which covers the join and leave events of the kick.
+const KICKED_TTL: Duration = Duration::from_secs(30);
+
+/// Join-rate tracking and the lockdown it triggers.
+#[derive(Default)]
+pub struct JoinFlood {
+    recent: VecDeque<Instant>,
+    lockdown_until: Option<Instant>,
+    kicked_count: u64,
+    /// Players recently kicked by the lockdown, so the other join and leave handlers skip them.
+    kicked: HashMap<Uuid, Instant>,
+}
+
+impl JoinFlood {
+    pub fn in_lockdown(&self) -> bool {
+        self.lockdown_until.is_some()
+    }
+
+    pub fn is_kicked(&self, uuid: Uuid) -> bool {
+        self.kicked
+            .get(&uuid)
+            .is_some_and(|kicked| kicked.elapsed() < KICKED_TTL)
+    }
+
+    fn prune_kicked(&mut self) {
+        self.kicked.retain(|_, kicked| kicked.elapsed() < KICKED_TTL);
+    }
+
+    /// Records a join and returns true when this join starts a new lockdown.
+    fn record_join(&mut self, now: Instant, max_per_second: usize, lockdown: Duration) -> bool {
+        while let Some(front) = self.recent.front() {
+            if now.duration_since(*front) > RATE_WINDOW {
+                self.recent.pop_front();
+            } else {
+                break;
+            }
+        }
+        self.recent.push_back(now);
+
+        if max_per_second == 0 || self.recent.len() <= max_per_second {
+            return false;
+        }
+        // Every join over the limit pushes the lift time back while the wave continues.
+        let started = self.lockdown_until.is_none();
+        self.lockdown_until = Some(now + lockdown);
+        if started {
+            self.kicked_count = 0;
+        }
+        started
+    }
+}
+
+pub type JoinFloodState = Arc<RwLock<JoinFlood>>;
+
+async fn alert(server: &Server, state: &PluginState, text: String) {
+    let message = branding::brand(TextComponent::text(text.clone()).color_named(NamedColor::Red));
+    notify_staff(server, PERMISSION_JOINFLOOD_ALERT, &message).await;
+
+    let config = {
+        let guard = state.config.read().unwrap();
+        guard.clone()
+    };
+    send_discord_message(
+        state.discord_bridge.as_ref(),
+        &config,
+        DiscordRoute::StaffAlert,
+        escape_markdown(&text),
+    );
+    send_webhook_embed(&config, escape_markdown(&text), ALERT_COLOR);
+}
+
+/// Must run before the join handlers that record players in the seen database.
+pub struct JoinFloodHandler {
+    state: Arc<PluginState>,
+}
+
+impl JoinFloodHandler {
+    pub fn new(state: Arc<PluginState>) -> Self {
+        Self { state }
+    }
+}
+
+impl EventHandler<PlayerJoinEvent> for JoinFloodHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        server: &'a Arc<Server>,
+        event: &'a mut PlayerJoinEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let config = {
+                let guard = self.state.config.read().unwrap();
+                guard.clone()
+            };
+            // A player kicked earlier who gets in this time is handled like anyone else.
+            let uuid = event.player.gameprofile.id;
+            self.state.join_flood.write().unwrap().kicked.remove(&uuid);
+            if !config.joinflood_enabled {
+                return;
+            }
+
+            let started = self.state.join_flood.write().unwrap().record_join(
+                Instant::now(),
+                config.joinflood_max_joins_per_second,
+                Duration::from_secs(config.joinflood_lockdown_seconds),
+            );
+            if started {
+                alert(
+                    server,
+                    &self.state,
+                    format!(
+                        "Join flood detected: lockdown enabled for {}s. Unknown players will be \
+                         kicked.",
+                        config.joinflood_lockdown_seconds
+                    ),
+                )
+                .await;
+            }
+
+            if !self.state.join_flood.read().unwrap().in_lockdown() {
+                return;
+            }
+            if self.state.seen.read().unwrap().contains_key(&uuid) {
+                return;
+            }
+
+            {
+                let mut flood = self.state.join_flood.write().unwrap();
+                flood.kicked.insert(uuid, Instant::now());
+                flood.kicked_count += 1;
+            }
+            event.set_cancelled(true);
+            let reason = TextComponent::text(config.joinflood_kick_message.clone());
+            event.player.kick(DisconnectReason::Kicked, reason).await;
+        })
+    }
+}
+
+/// Lifts the lockdown once the configured time has passed without further floods, and forgets
+/// kicked players once their events are over.
+pub fn start_joinflood_task(server: Arc<Server>, state: Arc<PluginState>) {
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().expect("joinflood runtime");
+
+        loop {
+            if SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+
+            let lifted = {
+                let mut flood = state.join_flood.write().unwrap();
+                flood.prune_kicked();
+                match flood.lockdown_until {
+                    Some(until) if Instant::now() >= until => {
+                        flood.lockdown_until = None;
+                        Some(flood.kicked_count)
+                    }
+                    _ => None,
+                }
+            };
+            if let Some(kicked) = lifted {
+                runtime.block_on(alert(
+                    &server,
+                    &state,
+                    format!("Join-flood lockdown lifted ({kicked} unknown players kicked)."),
+                ));
+            }
+
+            thread::sleep(LIFT_CHECK_INTERVAL);
+        }
+    });
+}
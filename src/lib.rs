@@ -1,40 +1,106 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 use pumpkin::plugin::{
-    BoxFuture, Context, EventHandler, EventPriority, Plugin, PluginFuture, PluginMetadata,
-    PLUGIN_API_VERSION,
+    BoxFuture, Cancellable, Context, EventHandler, EventPriority, Plugin, PluginFuture,
+    PluginMetadata, PLUGIN_API_VERSION,
 };
+use pumpkin::plugin::events::player::player_change_world::PlayerChangeWorldEvent;
 use pumpkin::plugin::events::player::player_chat::PlayerChatEvent;
+use pumpkin::plugin::events::player::player_command_send::PlayerCommandSendEvent;
 use pumpkin::plugin::events::player::player_join::PlayerJoinEvent;
 use pumpkin::plugin::events::player::player_leave::PlayerLeaveEvent;
+use pumpkin::plugin::events::player::player_death::PlayerDeathEvent;
+use pumpkin::plugin::events::player::player_login::PlayerLoginEvent;
+use pumpkin::plugin::events::player::player_move::PlayerMoveEvent;
+use pumpkin::plugin::events::player::player_teleport::PlayerTeleportEvent;
 use pumpkin::server::Server;
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::{TextComponent, TextContent};
 use pumpkin_util::permission::{Permission, PermissionDefault, PermissionLvl};
 
+mod advertising;
+mod alts;
 mod antispam;
+mod audit;
+mod bans;
 mod branding;
+mod chatcontrol;
+mod commandspy;
 mod chatfilter;
 mod chatformat;
 mod config;
 mod commands;
 mod scoreboard;
 mod seen;
+mod snapshots;
+mod staff;
+mod vanish;
 mod webhook;
 mod metrics;
 mod discord_bot;
-
+mod discord_commands;
+mod discord_console;
+mod discord_outbound;
+mod discord_render;
+mod discord_status;
+mod freeze;
+mod inspect;
+mod joinflood;
+mod lifecycle;
+mod links;
+mod mutes;
+mod notes;
+mod reports;
+mod warnings;
+
+use advertising::{
+    AdvertAllowlist, AdvertisingHandler, Allowlist, PERMISSION_ADVERT_BYPASS_DOMAIN, PERMISSION_ADVERT_BYPASS_INVITE,
+    PERMISSION_ADVERT_BYPASS_IP, PERMISSION_ADVERT_BYPASS_URL,
+};
 use antispam::{AntiSpamHandler, PERMISSION_ANTISPAM_BYPASS};
+use alts::{AltJoinHandler, PERMISSION_ALTS_ALERT, address_key, load_or_create_salt};
+use audit::AuditLog;
+use bans::{BanEntry, BanLoginHandler, bans_path, load_bans};
+use links::{LinkJoinHandler, LinkStore, load_links, links_path};
+use reports::{PERMISSION_REPORTS, ReportStore, load_reports, reports_path};
+use notes::{NoteStore, PERMISSION_NOTES, load_notes, notes_path};
+use vanish::{
+    PERMISSION_SEE_VANISHED, VanishJoinHandler, VanishRespawnHandler, VanishSet, is_vanished,
+    load_vanished, vanish_path,
+};
+use warnings::{WarningJoinHandler, WarningStore, load_warnings, warnings_path};
+use chatcontrol::{ChatControl, ChatControlHandler, ChatControlState, PERMISSION_CHAT_BYPASS};
+use commandspy::{
+    BlockedCommandHandler, CommandSpies, CommandSpyHandler, PERMISSION_BLOCKED_COMMANDS_BYPASS,
+    PERMISSION_COMMANDSPY,
+};
 use chatfilter::{ChatFilterHandler, PERMISSION_CHATFILTER_BYPASS};
 use chatformat::ChatFormatHandler;
+use inspect::PERMISSION_INVSEE_EDIT;
+use joinflood::{
+    JoinFlood, JoinFloodHandler, JoinFloodState, PERMISSION_JOINFLOOD_ALERT, start_joinflood_task,
+};
+use freeze::{
+    FreezeCommandHandler, FreezeLeaveHandler, FreezeMoveHandler, FrozenMap,
+    PERMISSION_FREEZE_ALERT, start_freeze_notice_task,
+};
 use config::Config;
 use scoreboard::start_scoreboard_task;
-use seen::{SeenEntry, update_on_join, update_on_leave};
+use snapshots::{SnapshotDeathHandler, SnapshotLeaveHandler};
+use seen::{
+    SeenEntry, flush_seen, load_seen, seen_path, start_seen_save_task, update_on_join,
+    update_on_leave,
+};
 use metrics::{start_system_sampler, SystemMetrics};
 use discord_bot::{DiscordBridge, DiscordEvent, start_discord_bot, send_discord_event};
+use discord_console::start_console_log_task;
+use discord_status::start_discord_status_task;
+use lifecycle::{announce_start, announce_stop};
 use webhook::{send_webhook, WebhookEvent};
+use mutes::{MuteChatHandler, MuteCommandHandler, MuteEntry, load_mutes, mutes_path};
 
 const PERMISSION_ADMIN: &str = "JinxCore:admin";
 const PERMISSION_GMC: &str = "JinxCore:gmc";
@@ -77,26 +143,67 @@ const PERMISSION_STARTERKIT: &str = "JinxCore:starterkit";
 const PERMISSION_DELSTARTERKIT: &str = "JinxCore:delstarterkit";
 const PERMISSION_TPS: &str = "JinxCore:tps";
 const PERMISSION_UPTIME: &str = "JinxCore:uptime";
+const PERMISSION_MUTE: &str = "JinxCore:mute";
+const PERMISSION_UNMUTE: &str = "JinxCore:unmute";
+const PERMISSION_MUTELIST: &str = "JinxCore:mutelist";
+const PERMISSION_BAN: &str = "JinxCore:ban";
+const PERMISSION_TEMPBAN: &str = "JinxCore:tempban";
+const PERMISSION_IPBAN: &str = "JinxCore:ipban";
+const PERMISSION_UNBAN: &str = "JinxCore:unban";
+const PERMISSION_BANLIST: &str = "JinxCore:banlist";
+const PERMISSION_WARN: &str = "JinxCore:warn";
+const PERMISSION_WARNINGS: &str = "JinxCore:warnings";
+const PERMISSION_DELWARN: &str = "JinxCore:delwarn";
+const PERMISSION_ALTS: &str = "JinxCore:alts";
+const PERMISSION_VANISH: &str = "JinxCore:vanish";
+const PERMISSION_FREEZE: &str = "JinxCore:freeze";
+const PERMISSION_INVSEE: &str = "JinxCore:invsee";
+const PERMISSION_ENDERSEE: &str = "JinxCore:endersee";
+const PERMISSION_CHAT: &str = "JinxCore:chat";
+const PERMISSION_REPORT: &str = "JinxCore:report";
+const PERMISSION_INVHISTORY: &str = "JinxCore:invhistory";
+const PERMISSION_INVRESTORE: &str = "JinxCore:invrestore";
+const PERMISSION_LINK: &str = "JinxCore:link";
 
 struct PluginState {
     config: Arc<RwLock<Config>>,
     data_dir: PathBuf,
     start_time: Instant,
     seen: Arc<RwLock<HashMap<uuid::Uuid, SeenEntry>>>,
+    seen_dirty: Arc<AtomicBool>,
     kit_cooldowns: Arc<RwLock<HashMap<uuid::Uuid, HashMap<String, Instant>>>>,
     system_metrics: Arc<RwLock<SystemMetrics>>,
     discord_bridge: Option<DiscordBridge>,
     playtime_total_secs: Arc<RwLock<HashMap<uuid::Uuid, u64>>>,
     playtime_session_start: Arc<RwLock<HashMap<uuid::Uuid, Instant>>>,
+    mutes: Arc<RwLock<HashMap<uuid::Uuid, MuteEntry>>>,
+    bans: Arc<RwLock<Vec<BanEntry>>>,
+    warnings: Arc<RwLock<WarningStore>>,
+    notes: Arc<RwLock<NoteStore>>,
+    address_salt: String,
+    audit: AuditLog,
+    vanished: VanishSet,
+    frozen: FrozenMap,
+    chat_control: ChatControlState,
+    reports: Arc<RwLock<ReportStore>>,
+    report_cooldowns: Arc<RwLock<HashMap<uuid::Uuid, Instant>>>,
+    command_spies: CommandSpies,
+    join_flood: JoinFloodState,
+    links: Arc<RwLock<LinkStore>>,
+    advert_allowlist: AdvertAllowlist,
 }
 
 struct JoinMessageHandler {
     config: Arc<RwLock<Config>>,
     seen: Arc<RwLock<HashMap<uuid::Uuid, SeenEntry>>>,
+    seen_dirty: Arc<AtomicBool>,
     discord: Option<DiscordBridge>,
     playtime_total_secs: Arc<RwLock<HashMap<uuid::Uuid, u64>>>,
     playtime_session_start: Arc<RwLock<HashMap<uuid::Uuid, Instant>>>,
     data_dir: PathBuf,
+    address_salt: String,
+    vanished: VanishSet,
+    join_flood: JoinFloodState,
 }
 
 impl EventHandler<PlayerJoinEvent> for JoinMessageHandler {
@@ -111,8 +218,14 @@ impl EventHandler<PlayerJoinEvent> for JoinMessageHandler {
                 guard.clone()
             };
 
+            if self.join_flood.read().unwrap().is_kicked(event.player.gameprofile.id) {
+                event.set_cancelled(true);
+                return;
+            }
+
             let name_component = TextComponent::text(event.player.gameprofile.name.clone());
-            let address = event.player.client.address().await.to_string();
+            let ip = event.player.client.address().await.ip().to_string();
+            let address = address_key(&config, &self.address_salt, &ip);
             {
                 let mut seen = self.seen.write().unwrap();
                 update_on_join(
@@ -121,6 +234,7 @@ impl EventHandler<PlayerJoinEvent> for JoinMessageHandler {
                     event.player.gameprofile.name.clone(),
                     Some(address),
                 );
+                self.seen_dirty.store(true, Ordering::Relaxed);
             }
             {
                 let mut sessions = self.playtime_session_start.write().unwrap();
@@ -128,6 +242,12 @@ impl EventHandler<PlayerJoinEvent> for JoinMessageHandler {
                 let mut totals = self.playtime_total_secs.write().unwrap();
                 totals.entry(event.player.gameprofile.id).or_insert(0);
             }
+            commands::apply_starterkit(&event.player, &self.data_dir).await;
+
+            if is_vanished(&self.vanished, event.player.gameprofile.id) {
+                event.set_cancelled(true);
+                return;
+            }
             send_webhook(
                 &config,
                 WebhookEvent::Join,
@@ -141,7 +261,6 @@ impl EventHandler<PlayerJoinEvent> for JoinMessageHandler {
                 &event.player.gameprofile.name,
                 None,
             );
-            commands::apply_starterkit(&event.player, &self.data_dir).await;
 
             if !config.join_enabled {
                 return;
@@ -158,9 +277,12 @@ impl EventHandler<PlayerJoinEvent> for JoinMessageHandler {
 struct LeaveMessageHandler {
     config: Arc<RwLock<Config>>,
     seen: Arc<RwLock<HashMap<uuid::Uuid, SeenEntry>>>,
+    seen_dirty: Arc<AtomicBool>,
     discord: Option<DiscordBridge>,
     playtime_total_secs: Arc<RwLock<HashMap<uuid::Uuid, u64>>>,
     playtime_session_start: Arc<RwLock<HashMap<uuid::Uuid, Instant>>>,
+    vanished: VanishSet,
+    join_flood: JoinFloodState,
 }
 
 impl EventHandler<PlayerLeaveEvent> for LeaveMessageHandler {
@@ -175,6 +297,12 @@ impl EventHandler<PlayerLeaveEvent> for LeaveMessageHandler {
                 guard.clone()
             };
 
+            // Players kicked by a join-flood lockdown never made it into the seen database.
+            if self.join_flood.read().unwrap().is_kicked(event.player.gameprofile.id) {
+                event.set_cancelled(true);
+                return;
+            }
+
             let name_component = TextComponent::text(event.player.gameprofile.name.clone());
             {
                 let mut seen = self.seen.write().unwrap();
@@ -183,6 +311,7 @@ impl EventHandler<PlayerLeaveEvent> for LeaveMessageHandler {
                     event.player.gameprofile.id,
                     event.player.gameprofile.name.clone(),
                 );
+                self.seen_dirty.store(true, Ordering::Relaxed);
             }
             {
                 let mut sessions = self.playtime_session_start.write().unwrap();
@@ -193,6 +322,11 @@ impl EventHandler<PlayerLeaveEvent> for LeaveMessageHandler {
                     *entry += elapsed;
                 }
             }
+
+            if is_vanished(&self.vanished, event.player.gameprofile.id) {
+                event.set_cancelled(true);
+                return;
+            }
             send_webhook(
                 &config,
                 WebhookEvent::Leave,
@@ -219,7 +353,62 @@ impl EventHandler<PlayerLeaveEvent> for LeaveMessageHandler {
     }
 }
 
-struct JinxUtilitiesPlugin;
+struct DeathMessageHandler {
+    config: Arc<RwLock<Config>>,
+    discord: Option<DiscordBridge>,
+    vanished: VanishSet,
+}
+
+/// Vanilla death messages are translations whose second argument, when present, is the killer.
+fn death_killer(message: &TextComponent) -> Option<String> {
+    match message.0.content.as_ref() {
+        TextContent::Translate { with, .. } => {
+            with.get(1).map(|killer| TextComponent(killer.clone()).get_text())
+        }
+        _ => None,
+    }
+}
+
+impl EventHandler<PlayerDeathEvent> for DeathMessageHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerDeathEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if is_vanished(&self.vanished, event.player.gameprofile.id) {
+                return;
+            }
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+
+            let message = event.death_message.clone().get_text();
+            let killer = death_killer(&event.death_message);
+            send_webhook(
+                &config,
+                WebhookEvent::Death {
+                    killer: killer.clone(),
+                },
+                &event.player.gameprofile.name,
+                Some(&message),
+            );
+            send_discord_event(
+                self.discord.as_ref(),
+                &config,
+                DiscordEvent::Death { killer },
+                &event.player.gameprofile.name,
+                Some(&message),
+            );
+        })
+    }
+}
+
+struct JinxUtilitiesPlugin {
+    /// Set by `on_load`, for the shutdown work in `on_unload`.
+    state: Option<Arc<PluginState>>,
+}
 
 // command trees live in src/commands/*
 
@@ -230,18 +419,79 @@ impl Plugin for JinxUtilitiesPlugin {
             let config = config::load_or_create(&data_dir)?;
             let discord_bridge = start_discord_bot(&config, Arc::clone(&server.server));
             print_startup_banner(&data_dir);
+            let mutes = load_mutes(&mutes_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read mutes.yml: {err}");
+                HashMap::new()
+            });
+            let bans = load_bans(&bans_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read bans.yml: {err}");
+                Vec::new()
+            });
+            let warnings = load_warnings(&warnings_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read warnings.yml: {err}");
+                WarningStore::default()
+            });
+            let notes = load_notes(&notes_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read notes.yml: {err}");
+                NoteStore::default()
+            });
+            let reports = load_reports(&reports_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read reports.yml: {err}");
+                ReportStore::default()
+            });
+            let links = load_links(&links_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read links.yml: {err}");
+                LinkStore::default()
+            });
+            let seen = load_seen(&seen_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read seen.yml: {err}");
+                HashMap::new()
+            });
+            let vanished = load_vanished(&vanish_path(&data_dir)).unwrap_or_else(|err| {
+                log::warn!("Failed to read vanished.yml: {err}");
+                HashSet::new()
+            });
+            let address_salt = load_or_create_salt(&data_dir);
+            let advert_allowlist = Arc::new(RwLock::new(Allowlist::load(
+                &data_dir,
+                &config.advert_allowed_domains,
+            )));
             let config = Arc::new(RwLock::new(config));
+            let audit = AuditLog::new(
+                Arc::clone(&config),
+                data_dir.clone(),
+                discord_bridge.clone(),
+            );
             let state = Arc::new(PluginState {
                 config: Arc::clone(&config),
                 data_dir,
                 start_time: Instant::now(),
-                seen: Arc::new(RwLock::new(HashMap::new())),
+                seen: Arc::new(RwLock::new(seen)),
+                seen_dirty: Arc::new(AtomicBool::new(false)),
                 kit_cooldowns: Arc::new(RwLock::new(HashMap::new())),
                 system_metrics: Arc::new(RwLock::new(SystemMetrics::default())),
                 discord_bridge,
                 playtime_total_secs: Arc::new(RwLock::new(HashMap::new())),
                 playtime_session_start: Arc::new(RwLock::new(HashMap::new())),
+                mutes: Arc::new(RwLock::new(mutes)),
+                bans: Arc::new(RwLock::new(bans)),
+                warnings: Arc::new(RwLock::new(warnings)),
+                notes: Arc::new(RwLock::new(notes)),
+                address_salt,
+                audit,
+                vanished: Arc::new(RwLock::new(vanished)),
+                frozen: Arc::new(RwLock::new(HashMap::new())),
+                chat_control: Arc::new(RwLock::new(ChatControl::default())),
+                reports: Arc::new(RwLock::new(reports)),
+                report_cooldowns: Arc::new(RwLock::new(HashMap::new())),
+                command_spies: Arc::new(RwLock::new(HashSet::new())),
+                join_flood: Arc::new(RwLock::new(JoinFlood::default())),
+                links: Arc::new(RwLock::new(links)),
+                advert_allowlist,
             });
+            if let Some(bridge) = &state.discord_bridge {
+                bridge.attach_state(Arc::clone(&state));
+            }
 
             let admin_permission = Permission::new(
                 PERMISSION_ADMIN,
@@ -530,6 +780,223 @@ impl Plugin for JinxUtilitiesPlugin {
             );
             server.register_permission(delstarterkit_permission).await.ok();
 
+            let mute_permission = Permission::new(
+                PERMISSION_MUTE,
+                "Mute a player.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(mute_permission).await.ok();
+
+            let unmute_permission = Permission::new(
+                PERMISSION_UNMUTE,
+                "Unmute a player.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(unmute_permission).await.ok();
+
+            let mutelist_permission = Permission::new(
+                PERMISSION_MUTELIST,
+                "List muted players.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(mutelist_permission).await.ok();
+
+            let ban_permission = Permission::new(
+                PERMISSION_BAN,
+                "Ban a player.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(ban_permission).await.ok();
+
+            let tempban_permission = Permission::new(
+                PERMISSION_TEMPBAN,
+                "Temporarily ban a player.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(tempban_permission).await.ok();
+
+            let ipban_permission = Permission::new(
+                PERMISSION_IPBAN,
+                "Ban a player's address.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(ipban_permission).await.ok();
+
+            let unban_permission = Permission::new(
+                PERMISSION_UNBAN,
+                "Remove a ban.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(unban_permission).await.ok();
+
+            let banlist_permission = Permission::new(
+                PERMISSION_BANLIST,
+                "List active bans.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(banlist_permission).await.ok();
+
+            let warn_permission = Permission::new(
+                PERMISSION_WARN,
+                "Warn a player.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(warn_permission).await.ok();
+
+            let warnings_permission = Permission::new(
+                PERMISSION_WARNINGS,
+                "View a player's warnings.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(warnings_permission).await.ok();
+
+            let delwarn_permission = Permission::new(
+                PERMISSION_DELWARN,
+                "Delete a warning.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(delwarn_permission).await.ok();
+
+            let notes_permission = Permission::new(
+                PERMISSION_NOTES,
+                "Manage and view staff notes.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(notes_permission).await.ok();
+
+            let alts_permission = Permission::new(
+                PERMISSION_ALTS,
+                "List accounts that share an address with a player.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(alts_permission).await.ok();
+
+            let alts_alert_permission = Permission::new(
+                PERMISSION_ALTS_ALERT,
+                "Receive join alerts for alts of banned or muted players.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(alts_alert_permission).await.ok();
+
+            let vanish_permission = Permission::new(
+                PERMISSION_VANISH,
+                "Toggle vanish mode.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(vanish_permission).await.ok();
+
+            let see_vanished_permission = Permission::new(
+                PERMISSION_SEE_VANISHED,
+                "See vanished players.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(see_vanished_permission).await.ok();
+
+            let freeze_permission = Permission::new(
+                PERMISSION_FREEZE,
+                "Freeze or unfreeze a player.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(freeze_permission).await.ok();
+
+            let freeze_alert_permission = Permission::new(
+                PERMISSION_FREEZE_ALERT,
+                "Receive alerts when a frozen player disconnects.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(freeze_alert_permission).await.ok();
+
+            let invsee_permission = Permission::new(
+                PERMISSION_INVSEE,
+                "View a player's inventory.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(invsee_permission).await.ok();
+
+            let endersee_permission = Permission::new(
+                PERMISSION_ENDERSEE,
+                "View a player's ender chest.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(endersee_permission).await.ok();
+
+            let invsee_edit_permission = Permission::new(
+                PERMISSION_INVSEE_EDIT,
+                "Move items in /invsee and /endersee views.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(invsee_edit_permission).await.ok();
+
+            let chat_permission = Permission::new(
+                PERMISSION_CHAT,
+                "Lock chat or enable slow mode.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(chat_permission).await.ok();
+
+            let chat_bypass_permission = Permission::new(
+                PERMISSION_CHAT_BYPASS,
+                "Bypass chat lock and slow mode.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(chat_bypass_permission).await.ok();
+
+            let report_permission = Permission::new(
+                PERMISSION_REPORT,
+                "Report a player to staff.",
+                PermissionDefault::Allow,
+            );
+            server.register_permission(report_permission).await.ok();
+
+            let reports_permission = Permission::new(
+                PERMISSION_REPORTS,
+                "View and manage player reports.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(reports_permission).await.ok();
+
+            let commandspy_permission = Permission::new(
+                PERMISSION_COMMANDSPY,
+                "Use /commandspy to see other players' commands.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(commandspy_permission).await.ok();
+
+            let blocked_commands_bypass_permission = Permission::new(
+                PERMISSION_BLOCKED_COMMANDS_BYPASS,
+                "Run commands listed in blocked_commands.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(blocked_commands_bypass_permission).await.ok();
+
+            let joinflood_alert_permission = Permission::new(
+                PERMISSION_JOINFLOOD_ALERT,
+                "Receive join-flood lockdown alerts.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(joinflood_alert_permission).await.ok();
+
+            let invhistory_permission = Permission::new(
+                PERMISSION_INVHISTORY,
+                "List inventory snapshots.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(invhistory_permission).await.ok();
+
+            let invrestore_permission = Permission::new(
+                PERMISSION_INVRESTORE,
+                "Restore inventory snapshots.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(invrestore_permission).await.ok();
+
+            let link_permission = Permission::new(
+                PERMISSION_LINK,
+                "Link a Discord account.",
+                PermissionDefault::Allow,
+            );
+            server.register_permission(link_permission).await.ok();
+
             let bypass_permission = Permission::new(
                 PERMISSION_ANTISPAM_BYPASS,
                 "Bypass the anti-spam filter.",
@@ -547,6 +1014,34 @@ impl Plugin for JinxUtilitiesPlugin {
                 .await
                 .ok();
 
+            let advert_url_bypass_permission = Permission::new(
+                PERMISSION_ADVERT_BYPASS_URL,
+                "Bypass the URL advertising filter.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(advert_url_bypass_permission).await.ok();
+
+            let advert_domain_bypass_permission = Permission::new(
+                PERMISSION_ADVERT_BYPASS_DOMAIN,
+                "Bypass the domain advertising filter.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(advert_domain_bypass_permission).await.ok();
+
+            let advert_ip_bypass_permission = Permission::new(
+                PERMISSION_ADVERT_BYPASS_IP,
+                "Bypass the IP address advertising filter.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(advert_ip_bypass_permission).await.ok();
+
+            let advert_invite_bypass_permission = Permission::new(
+                PERMISSION_ADVERT_BYPASS_INVITE,
+                "Bypass the Discord invite advertising filter.",
+                PermissionDefault::Op(PermissionLvl::Two),
+            );
+            server.register_permission(advert_invite_bypass_permission).await.ok();
+
             server
                 .register_command(commands::jinx_command_tree(Arc::clone(&state)), PERMISSION_ADMIN)
                 .await;
@@ -566,7 +1061,10 @@ impl Plugin for JinxUtilitiesPlugin {
                 .register_command(commands::whois_command_tree(Arc::clone(&state)), PERMISSION_WHOIS)
                 .await;
             server
-                .register_command(commands::clearinv_command_tree(), PERMISSION_CLEARINV)
+                .register_command(
+                    commands::clearinv_command_tree(Arc::clone(&state)),
+                    PERMISSION_CLEARINV,
+                )
                 .await;
             server
                 .register_command(commands::rules_command_tree(Arc::clone(&state)), PERMISSION_RULES)
@@ -596,10 +1094,10 @@ impl Plugin for JinxUtilitiesPlugin {
                 )
                 .await;
             server
-                .register_command(commands::speed_command_tree(), PERMISSION_SPEED)
+                .register_command(commands::speed_command_tree(&state.audit), PERMISSION_SPEED)
                 .await;
             server
-                .register_command(commands::near_command_tree(), PERMISSION_NEAR)
+                .register_command(commands::near_command_tree(Arc::clone(&state)), PERMISSION_NEAR)
                 .await;
             server
                 .register_command(
@@ -626,31 +1124,34 @@ impl Plugin for JinxUtilitiesPlugin {
                 .register_command(commands::playtime_command_tree(Arc::clone(&state)), PERMISSION_PLAYTIME)
                 .await;
             server
-                .register_command(commands::clearchat_command_tree(), PERMISSION_CLEARCHAT)
+                .register_command(
+                    commands::clearchat_command_tree(&state.audit),
+                    PERMISSION_CLEARCHAT,
+                )
                 .await;
             server
                 .register_command(commands::me_command_tree(), PERMISSION_ME)
                 .await;
             server
-                .register_command(commands::day_command_tree(), PERMISSION_DAY)
+                .register_command(commands::day_command_tree(&state.audit), PERMISSION_DAY)
                 .await;
             server
-                .register_command(commands::night_command_tree(), PERMISSION_NIGHT)
+                .register_command(commands::night_command_tree(&state.audit), PERMISSION_NIGHT)
                 .await;
             server
-                .register_command(commands::rain_command_tree(), PERMISSION_RAIN)
+                .register_command(commands::rain_command_tree(&state.audit), PERMISSION_RAIN)
                 .await;
             server
-                .register_command(commands::clear_command_tree(), PERMISSION_CLEAR)
+                .register_command(commands::clear_command_tree(&state.audit), PERMISSION_CLEAR)
                 .await;
             server
-                .register_command(commands::thunder_command_tree(), PERMISSION_THUNDER)
+                .register_command(commands::thunder_command_tree(&state.audit), PERMISSION_THUNDER)
                 .await;
             server
                 .register_command(commands::calc_command_tree(), PERMISSION_CALC)
                 .await;
             server
-                .register_command(commands::online_command_tree(), PERMISSION_ONLINE)
+                .register_command(commands::online_command_tree(Arc::clone(&state)), PERMISSION_ONLINE)
                 .await;
             server
                 .register_command(commands::flip_command_tree(), PERMISSION_FLIP)
@@ -659,7 +1160,10 @@ impl Plugin for JinxUtilitiesPlugin {
                 .register_command(commands::plugins_alias_command_tree(), PERMISSION_PLUGINS)
                 .await;
             server
-                .register_command(commands::give_alias_command_tree(), PERMISSION_GIVE_ALIAS)
+                .register_command(
+                    commands::give_alias_command_tree(&state.audit),
+                    PERMISSION_GIVE_ALIAS,
+                )
                 .await;
             server
                 .register_command(
@@ -674,84 +1178,398 @@ impl Plugin for JinxUtilitiesPlugin {
                 )
                 .await;
             server
-                .register_command(commands::gmc_command_tree(), PERMISSION_GMC)
+                .register_command(commands::gmc_command_tree(&state.audit), PERMISSION_GMC)
+                .await;
+            server
+                .register_command(commands::gms_command_tree(&state.audit), PERMISSION_GMS)
+                .await;
+            server
+                .register_command(commands::gmsp_command_tree(&state.audit), PERMISSION_GMSP)
+                .await;
+            server
+                .register_command(commands::gma_command_tree(&state.audit), PERMISSION_GMA)
+                .await;
+            server
+                .register_command(commands::creative_command_tree(&state.audit), PERMISSION_GMC)
+                .await;
+            server
+                .register_command(commands::survival_command_tree(&state.audit), PERMISSION_GMS)
+                .await;
+            server
+                .register_command(commands::spectator_command_tree(&state.audit), PERMISSION_GMSP)
+                .await;
+            server
+                .register_command(commands::adventure_command_tree(&state.audit), PERMISSION_GMA)
+                .await;
+            server
+                .register_command(commands::c_command_tree(&state.audit), PERMISSION_GMC)
+                .await;
+            server
+                .register_command(commands::s_command_tree(&state.audit), PERMISSION_GMS)
+                .await;
+            server
+                .register_command(commands::sp_command_tree(&state.audit), PERMISSION_GMSP)
+                .await;
+            server
+                .register_command(commands::a_command_tree(&state.audit), PERMISSION_GMA)
+                .await;
+            server
+                .register_command(commands::heal_command_tree(&state.audit), PERMISSION_HEAL)
+                .await;
+            server
+                .register_command(commands::feed_command_tree(&state.audit), PERMISSION_FEED)
+                .await;
+            server
+                .register_command(commands::fly_command_tree(&state.audit), PERMISSION_FLY)
+                .await;
+            server
+                .register_command(commands::god_command_tree(&state.audit), PERMISSION_GOD)
+                .await;
+            server
+                .register_command(commands::mute_command_tree(Arc::clone(&state)), PERMISSION_MUTE)
+                .await;
+            server
+                .register_command(
+                    commands::tempmute_command_tree(Arc::clone(&state)),
+                    PERMISSION_MUTE,
+                )
+                .await;
+            server
+                .register_command(
+                    commands::unmute_command_tree(Arc::clone(&state)),
+                    PERMISSION_UNMUTE,
+                )
+                .await;
+            server
+                .register_command(
+                    commands::mutelist_command_tree(Arc::clone(&state)),
+                    PERMISSION_MUTELIST,
+                )
+                .await;
+            server
+                .register_command(commands::ban_command_tree(Arc::clone(&state)), PERMISSION_BAN)
                 .await;
             server
-                .register_command(commands::gms_command_tree(), PERMISSION_GMS)
+                .register_command(
+                    commands::tempban_command_tree(Arc::clone(&state)),
+                    PERMISSION_TEMPBAN,
+                )
+                .await;
+            server
+                .register_command(
+                    commands::ipban_command_tree(Arc::clone(&state)),
+                    PERMISSION_IPBAN,
+                )
+                .await;
+            server
+                .register_command(
+                    commands::unban_command_tree(Arc::clone(&state)),
+                    PERMISSION_UNBAN,
+                )
+                .await;
+            server
+                .register_command(
+                    commands::banlist_command_tree(Arc::clone(&state)),
+                    PERMISSION_BANLIST,
+                )
+                .await;
+            server
+                .register_command(commands::warn_command_tree(Arc::clone(&state)), PERMISSION_WARN)
+                .await;
+            server
+                .register_command(
+                    commands::warnings_command_tree(Arc::clone(&state)),
+                    PERMISSION_WARNINGS,
+                )
+                .await;
+            server
+                .register_command(
+                    commands::delwarn_command_tree(Arc::clone(&state)),
+                    PERMISSION_DELWARN,
+                )
                 .await;
             server
-                .register_command(commands::gmsp_command_tree(), PERMISSION_GMSP)
+                .register_command(commands::note_command_tree(Arc::clone(&state)), PERMISSION_NOTES)
                 .await;
             server
-                .register_command(commands::gma_command_tree(), PERMISSION_GMA)
+                .register_command(commands::alts_command_tree(Arc::clone(&state)), PERMISSION_ALTS)
                 .await;
             server
-                .register_command(commands::creative_command_tree(), PERMISSION_GMC)
+                .register_command(
+                    commands::vanish_command_tree(Arc::clone(&state)),
+                    PERMISSION_VANISH,
+                )
                 .await;
             server
-                .register_command(commands::survival_command_tree(), PERMISSION_GMS)
+                .register_command(
+                    commands::freeze_command_tree(Arc::clone(&state)),
+                    PERMISSION_FREEZE,
+                )
                 .await;
             server
-                .register_command(commands::spectator_command_tree(), PERMISSION_GMSP)
+                .register_command(
+                    commands::invsee_command_tree(Arc::clone(&state)),
+                    PERMISSION_INVSEE,
+                )
                 .await;
             server
-                .register_command(commands::adventure_command_tree(), PERMISSION_GMA)
+                .register_command(
+                    commands::endersee_command_tree(Arc::clone(&state)),
+                    PERMISSION_ENDERSEE,
+                )
                 .await;
             server
-                .register_command(commands::c_command_tree(), PERMISSION_GMC)
+                .register_command(commands::chat_command_tree(Arc::clone(&state)), PERMISSION_CHAT)
                 .await;
             server
-                .register_command(commands::s_command_tree(), PERMISSION_GMS)
+                .register_command(
+                    commands::report_command_tree(Arc::clone(&state)),
+                    PERMISSION_REPORT,
+                )
                 .await;
             server
-                .register_command(commands::sp_command_tree(), PERMISSION_GMSP)
+                .register_command(
+                    commands::reports_command_tree(Arc::clone(&state)),
+                    PERMISSION_REPORTS,
+                )
                 .await;
             server
-                .register_command(commands::a_command_tree(), PERMISSION_GMA)
+                .register_command(
+                    commands::commandspy_command_tree(Arc::clone(&state)),
+                    PERMISSION_COMMANDSPY,
+                )
                 .await;
             server
-                .register_command(commands::heal_command_tree(), PERMISSION_HEAL)
+                .register_command(
+                    commands::invhistory_command_tree(Arc::clone(&state)),
+                    PERMISSION_INVHISTORY,
+                )
                 .await;
             server
-                .register_command(commands::feed_command_tree(), PERMISSION_FEED)
+                .register_command(
+                    commands::invrestore_command_tree(Arc::clone(&state)),
+                    PERMISSION_INVRESTORE,
+                )
                 .await;
             server
-                .register_command(commands::fly_command_tree(), PERMISSION_FLY)
+                .register_command(commands::link_command_tree(Arc::clone(&state)), PERMISSION_LINK)
                 .await;
             server
-                .register_command(commands::god_command_tree(), PERMISSION_GOD)
+                .register_command(
+                    commands::unlink_command_tree(Arc::clone(&state)),
+                    PERMISSION_LINK,
+                )
                 .await;
 
             start_scoreboard_task(Arc::clone(&server.server), Arc::clone(&state));
             start_system_sampler(Arc::clone(&state));
+            start_freeze_notice_task(Arc::clone(&server.server), Arc::clone(&state));
+            start_joinflood_task(Arc::clone(&server.server), Arc::clone(&state));
+            start_console_log_task(Arc::clone(&state));
+            start_discord_status_task(Arc::clone(&server.server), Arc::clone(&state));
+            start_seen_save_task(Arc::clone(&state));
 
+            server
+                .register_event::<PlayerLoginEvent, _>(
+                    Arc::new(BanLoginHandler::new(
+                        Arc::clone(&config),
+                        Arc::clone(&state.bans),
+                        state.data_dir.clone(),
+                        state.address_salt.clone(),
+                    )),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerJoinEvent, _>(
+                    Arc::new(JoinFloodHandler::new(Arc::clone(&state))),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
             server
                 .register_event::<PlayerJoinEvent, _>(
                     Arc::new(JoinMessageHandler {
                         config: Arc::clone(&config),
                         seen: Arc::clone(&state.seen),
+                        seen_dirty: Arc::clone(&state.seen_dirty),
                         discord: state.discord_bridge.clone(),
                         playtime_total_secs: Arc::clone(&state.playtime_total_secs),
                         playtime_session_start: Arc::clone(&state.playtime_session_start),
                         data_dir: state.data_dir.clone(),
+                        address_salt: state.address_salt.clone(),
+                        vanished: Arc::clone(&state.vanished),
+                        join_flood: Arc::clone(&state.join_flood),
                     }),
                     EventPriority::Normal,
                     true,
                 )
                 .await;
+            server
+                .register_event::<PlayerJoinEvent, _>(
+                    Arc::new(VanishJoinHandler::new(
+                        Arc::clone(&state.vanished),
+                        Arc::clone(&state.join_flood),
+                    )),
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
+            let vanish_respawn = Arc::new(VanishRespawnHandler::new(Arc::clone(&state.vanished)));
+            server
+                .register_event::<PlayerTeleportEvent, _>(
+                    Arc::clone(&vanish_respawn),
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerChangeWorldEvent, _>(
+                    vanish_respawn,
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerJoinEvent, _>(
+                    Arc::new(AltJoinHandler::new(Arc::clone(&state))),
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerJoinEvent, _>(
+                    Arc::new(WarningJoinHandler::new(
+                        Arc::clone(&state.warnings),
+                        state.data_dir.clone(),
+                        Arc::clone(&state.join_flood),
+                    )),
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerJoinEvent, _>(
+                    Arc::new(LinkJoinHandler::new(Arc::clone(&state))),
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
             server
                 .register_event::<PlayerLeaveEvent, _>(
                     Arc::new(LeaveMessageHandler {
                         config: Arc::clone(&config),
                         seen: Arc::clone(&state.seen),
+                        seen_dirty: Arc::clone(&state.seen_dirty),
                         discord: state.discord_bridge.clone(),
                         playtime_total_secs: Arc::clone(&state.playtime_total_secs),
                         playtime_session_start: Arc::clone(&state.playtime_session_start),
+                        vanished: Arc::clone(&state.vanished),
+                        join_flood: Arc::clone(&state.join_flood),
+                    }),
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerChatEvent, _>(
+                    Arc::new(MuteChatHandler::new(
+                        Arc::clone(&config),
+                        Arc::clone(&state.mutes),
+                        state.data_dir.clone(),
+                    )),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerCommandSendEvent, _>(
+                    Arc::new(BlockedCommandHandler::new(Arc::clone(&config))),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerCommandSendEvent, _>(
+                    Arc::new(CommandSpyHandler::new(
+                        Arc::clone(&config),
+                        Arc::clone(&state.command_spies),
+                    )),
+                    EventPriority::Lowest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerCommandSendEvent, _>(
+                    Arc::new(MuteCommandHandler::new(
+                        Arc::clone(&config),
+                        Arc::clone(&state.mutes),
+                        state.data_dir.clone(),
+                    )),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerCommandSendEvent, _>(
+                    Arc::new(FreezeCommandHandler::new(
+                        Arc::clone(&config),
+                        Arc::clone(&state.frozen),
+                    )),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerMoveEvent, _>(
+                    Arc::new(FreezeMoveHandler::new(Arc::clone(&state.frozen))),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerDeathEvent, _>(
+                    Arc::new(SnapshotDeathHandler::new(Arc::clone(&state))),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerDeathEvent, _>(
+                    Arc::new(DeathMessageHandler {
+                        config: Arc::clone(&config),
+                        discord: state.discord_bridge.clone(),
+                        vanished: Arc::clone(&state.vanished),
                     }),
+                    EventPriority::Lowest,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerLeaveEvent, _>(
+                    Arc::new(SnapshotLeaveHandler::new(Arc::clone(&state))),
                     EventPriority::Normal,
                     true,
                 )
                 .await;
+            server
+                .register_event::<PlayerLeaveEvent, _>(
+                    Arc::new(FreezeLeaveHandler::new(Arc::clone(&state.frozen))),
+                    EventPriority::Normal,
+                    true,
+                )
+                .await;
+            server
+                .register_event::<PlayerChatEvent, _>(
+                    Arc::new(ChatControlHandler::new(
+                        Arc::clone(&config),
+                        Arc::clone(&state.chat_control),
+                    )),
+                    EventPriority::Highest,
+                    true,
+                )
+                .await;
             server
                 .register_event::<PlayerChatEvent, _>(
                     Arc::new(AntiSpamHandler::new(Arc::clone(&config))),
@@ -766,6 +1584,16 @@ impl Plugin for JinxUtilitiesPlugin {
                     true,
                 )
                 .await;
+            server
+                .register_event::<PlayerChatEvent, _>(
+                    Arc::new(AdvertisingHandler::new(
+                        Arc::clone(&config),
+                        Arc::clone(&state.advert_allowlist),
+                    )),
+                    EventPriority::High,
+                    true,
+                )
+                .await;
             server
                 .register_event::<PlayerChatEvent, _>(
                     Arc::new(ChatFormatHandler::new(
@@ -776,9 +1604,27 @@ impl Plugin for JinxUtilitiesPlugin {
                     true,
                 )
                 .await;
+
+            announce_start(&state);
+            self.state = Some(state);
             Ok(())
         })
     }
+
+    fn on_unload(&mut self, _server: Arc<Context>) -> PluginFuture<'_, Result<(), String>> {
+        Box::pin(async move {
+            let Some(state) = self.state.take() else {
+                return Ok(());
+            };
+            // Waits for the disk, Discord and the webhook, so it stays off the async workers.
+            tokio::task::spawn_blocking(move || {
+                flush_seen(&state);
+                announce_stop(&state);
+            })
+                .await
+                .map_err(|err| err.to_string())
+        })
+    }
 }
 
 #[unsafe(no_mangle)]
@@ -794,7 +1640,7 @@ pub static PUMPKIN_API_VERSION: u32 = PLUGIN_API_VERSION;
 
 #[unsafe(no_mangle)]
 pub fn plugin() -> Box<dyn Plugin> {
-    Box::new(JinxUtilitiesPlugin)
+    Box::new(JinxUtilitiesPlugin { state: None })
 }
 
 fn print_startup_banner(data_dir: &PathBuf) {
@@ -0,0 +1,91 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use pumpkin_world::CURRENT_MC_VERSION;
+use sysinfo::System;
+
+use crate::PluginState;
+use crate::config::Config;
+use crate::discord_bot::{DiscordRoute, flush_discord, send_discord_message};
+use crate::discord_status::send_offline_topic;
+use crate::seen::unix_now;
+use crate::webhook::{WebhookEvent, send_webhook};
+
+/// Exists while the server runs; finding it on start means the last run did not stop cleanly.
+const MARKER_FILE_NAME: &str = "running.lock";
+const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn marker_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(MARKER_FILE_NAME)
+}
+
+/// Time since the server process started, which covers world loading before plugins.
+fn process_age() -> Option<Duration> {
+    let pid = sysinfo::get_current_pid().ok()?;
+    let mut system = System::new();
+    system.refresh_process(pid);
+    let started = system.process(pid)?.start_time();
+    Some(Duration::from_secs(unix_now().saturating_sub(started)))
+}
+
+/// Sends a notice through the bot and the webhook. Empty texts are skipped.
+fn notify(state: &PluginState, config: &Config, text: &str) -> Option<JoinHandle<()>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if config.discord_send_lifecycle {
+        send_discord_message(
+            state.discord_bridge.as_ref(),
+            config,
+            DiscordRoute::Server,
+            text.to_string(),
+        );
+    }
+    send_webhook(config, WebhookEvent::Server, "", Some(text))
+}
+
+/// Announces the start, after reporting an unexpected shutdown if the previous run left its
+/// marker behind.
+pub fn announce_start(state: &PluginState) {
+    let config = {
+        let guard = state.config.read().unwrap();
+        guard.clone()
+    };
+    let marker = marker_path(&state.data_dir);
+    if marker.exists() {
+        log::warn!("The previous run did not stop cleanly");
+        notify(state, &config, &config.lifecycle_unexpected_format);
+    }
+    if let Err(err) = fs::write(&marker, unix_now().to_string()) {
+        log::warn!("Failed to write {MARKER_FILE_NAME}: {err}");
+    }
+
+    let load_time = process_age().unwrap_or_else(|| state.start_time.elapsed());
+    let text = config
+        .lifecycle_started_format
+        .replace("{VERSION}", CURRENT_MC_VERSION)
+        .replace("{LOAD_TIME}", &format!("{}s", load_time.as_secs()));
+    notify(state, &config, &text);
+}
+
+/// Records the clean stop, announces it and switches the topic to offline, then waits for
+/// delivery. Runs from the plugin's unload, which the server awaits before exiting. Blocks.
+pub fn announce_stop(state: &PluginState) {
+    // Removed first so a slow notice cannot make the stop look unclean.
+    if let Err(err) = fs::remove_file(marker_path(&state.data_dir)) {
+        log::warn!("Failed to remove {MARKER_FILE_NAME}: {err}");
+    }
+    let config = {
+        let guard = state.config.read().unwrap();
+        guard.clone()
+    };
+    let webhook = notify(state, &config, &config.lifecycle_stopping_format);
+    send_offline_topic(state, &config);
+    flush_discord(state.discord_bridge.as_ref(), SHUTDOWN_FLUSH_TIMEOUT);
+    if let Some(webhook) = webhook {
+        let _ = webhook.join();
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use pumpkin::entity::player::Player;
+use pumpkin::plugin::{BoxFuture, EventHandler};
+use pumpkin::plugin::events::player::player_join::PlayerJoinEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{PluginState, branding};
+use crate::config::{Config, config_pairs};
+use crate::discord_bot::send_member_sync;
+use crate::discord_outbound::MemberSync;
+use crate::seen::unix_now;
+
+const LINKS_FILE_NAME: &str = "links.yml";
+/// No 0/O or 1/I, so codes survive being read off the screen.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LENGTH: usize = 6;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinkEntry {
+    pub discord_id: u64,
+    pub uuid: Uuid,
+    pub name: String,
+    pub linked_at: u64,
+    /// Roles whose `discord_role_commands` have run, so each runs once per grant and the
+    /// removal commands run when the role goes.
+    #[serde(default)]
+    pub command_roles: Vec<u64>,
+}
+
+#[derive(Clone, Debug)]
+struct PendingLink {
+    uuid: Uuid,
+    name: String,
+    created: Instant,
+}
+
+/// Linked accounts are persisted; link codes only live in memory.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LinkStore {
+    pub links: Vec<LinkEntry>,
+    #[serde(skip)]
+    pending: HashMap<String, PendingLink>,
+}
+
+impl LinkStore {
+    pub fn by_discord(&self, discord_id: u64) -> Option<&LinkEntry> {
+        self.links.iter().find(|entry| entry.discord_id == discord_id)
+    }
+
+    pub fn by_uuid(&self, uuid: Uuid) -> Option<&LinkEntry> {
+        self.links.iter().find(|entry| entry.uuid == uuid)
+    }
+
+    /// Issues a fresh code for the player, replacing any earlier one.
+    pub fn create_code(&mut self, uuid: Uuid, name: String, expiry: Duration) -> String {
+        self.pending
+            .retain(|_, pending| pending.uuid != uuid && pending.created.elapsed() < expiry);
+        let mut rng = rand::thread_rng();
+        let code = loop {
+            let code: String = (0..CODE_LENGTH)
+                .map(|_| char::from(CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())]))
+                .collect();
+            if !self.pending.contains_key(&code) {
+                break code;
+            }
+        };
+        self.pending.insert(
+            code.clone(),
+            PendingLink {
+                uuid,
+                name,
+                created: Instant::now(),
+            },
+        );
+        code
+    }
+
+    /// Consumes a code and links its player to the Discord user.
+    pub fn redeem(
+        &mut self,
+        code: &str,
+        discord_id: u64,
+        expiry: Duration,
+    ) -> Result<LinkEntry, String> {
+        if self.by_discord(discord_id).is_some() {
+            return Err("Your Discord account is already linked.".to_string());
+        }
+        let code = code.trim().to_ascii_uppercase();
+        let pending = match self.pending.remove(&code) {
+            Some(pending) if pending.created.elapsed() < expiry => pending,
+            _ => return Err("That code is invalid or has expired.".to_string()),
+        };
+        self.links.retain(|entry| entry.uuid != pending.uuid);
+        let entry = LinkEntry {
+            discord_id,
+            uuid: pending.uuid,
+            name: pending.name,
+            linked_at: unix_now(),
+            command_roles: Vec::new(),
+        };
+        self.links.push(entry.clone());
+        Ok(entry)
+    }
+
+    pub fn unlink(&mut self, uuid: Uuid) -> Option<LinkEntry> {
+        let index = self.links.iter().position(|entry| entry.uuid == uuid)?;
+        Some(self.links.remove(index))
+    }
+
+    /// Keeps the stored name current after a rename. Returns true when it changed.
+    pub fn rename(&mut self, uuid: Uuid, name: &str) -> bool {
+        match self.links.iter_mut().find(|entry| entry.uuid == uuid) {
+            Some(entry) if entry.name != name => {
+                entry.name = name.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn links_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(LINKS_FILE_NAME)
+}
+
+pub fn load_links(path: &Path) -> Result<LinkStore, String> {
+    if !path.exists() {
+        return Ok(LinkStore::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let store = serde_yaml::from_str::<LinkStore>(&content).map_err(|e| e.to_string())?;
+    Ok(store)
+}
+
+pub fn save_links(path: &Path, store: &LinkStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_yaml::to_string(store).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn role_removed_commands_for(config: &Config, entry: &LinkEntry) -> Vec<(u64, String)> {
+    config_pairs(&config.discord_role_removed_commands)
+        .into_iter()
+        .filter_map(|(role, command)| {
+            let role = role.parse::<u64>().ok()?;
+            Some((role, command.replace("{PLAYER}", &entry.name)))
+        })
+        .collect()
+}
+
+/// Works out which Discord roles the linked member should hold. Permission roles can only be
+/// decided while the player is online; otherwise they are left alone until the next join.
+pub async fn member_sync(
+    config: &Config,
+    entry: &LinkEntry,
+    player: Option<&Player>,
+) -> MemberSync {
+    let mut roles = Vec::new();
+    if config.discord_linked_role_id != 0 {
+        roles.push((config.discord_linked_role_id, true));
+    }
+    let mut role_commands = Vec::new();
+    let mut role_removed_commands = Vec::new();
+    if let Some(player) = player {
+        for (node, role) in config_pairs(&config.discord_permission_roles) {
+            let Ok(role) = role.parse::<u64>() else {
+                continue;
+            };
+            roles.push((role, player.has_permission(&node).await));
+        }
+        for (role, command) in config_pairs(&config.discord_role_commands) {
+            let Ok(role) = role.parse::<u64>() else {
+                continue;
+            };
+            role_commands.push((role, command.replace("{PLAYER}", &entry.name)));
+        }
+        role_removed_commands = role_removed_commands_for(config, entry);
+    }
+    MemberSync {
+        discord_id: entry.discord_id,
+        uuid: entry.uuid,
+        linked: true,
+        roles,
+        role_commands,
+        role_removed_commands,
+        command_roles: entry.command_roles.clone(),
+    }
+}
+
+/// Removes every synced role from a member whose link was removed and undoes their role
+/// commands.
+pub fn unlink_sync(config: &Config, entry: &LinkEntry) -> MemberSync {
+    let mut roles = Vec::new();
+    if config.discord_linked_role_id != 0 {
+        roles.push((config.discord_linked_role_id, false));
+    }
+    for (_, role) in config_pairs(&config.discord_permission_roles) {
+        if let Ok(role) = role.parse::<u64>() {
+            roles.push((role, false));
+        }
+    }
+    MemberSync {
+        discord_id: entry.discord_id,
+        uuid: entry.uuid,
+        linked: false,
+        roles,
+        role_commands: Vec::new(),
+        role_removed_commands: role_removed_commands_for(config, entry),
+        command_roles: entry.command_roles.clone(),
+    }
+}
+
+/// Tells the player about a new link and syncs their roles, including permission roles if
+/// they are online.
+pub async fn complete_link(server: &Server, state: &PluginState, entry: &LinkEntry) {
+    let config = {
+        let guard = state.config.read().unwrap();
+        guard.clone()
+    };
+    let player = server.get_player_by_name(&entry.name).await;
+    if let Some(player) = &player {
+        let notice = branding::brand(
+            TextComponent::text("Your account is now linked to Discord.")
+                .color_named(NamedColor::Green),
+        );
+        player.send_system_message(&notice).await;
+    }
+    let sync = member_sync(&config, entry, player.as_deref()).await;
+    send_member_sync(state.discord_bridge.as_ref(), sync);
+}
+
+pub struct LinkJoinHandler {
+    state: Arc<PluginState>,
+}
+
+impl LinkJoinHandler {
+    pub fn new(state: Arc<PluginState>) -> Self {
+        Self { state }
+    }
+}
+
+impl EventHandler<PlayerJoinEvent> for LinkJoinHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerJoinEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let uuid = event.player.gameprofile.id;
+            if self.state.join_flood.read().unwrap().is_kicked(uuid) {
+                return;
+            }
+            let name = event.player.gameprofile.name.clone();
+            let entry = {
+                let mut links = self.state.links.write().unwrap();
+                let saved = if links.rename(uuid, &name) {
+                    save_links(&links_path(&self.state.data_dir), &links)
+                } else {
+                    Ok(())
+                };
+                if let Err(err) = saved {
+                    log::warn!("Failed to save links.yml: {err}");
+                }
+                links.by_uuid(uuid).cloned()
+            };
+            let Some(entry) = entry else {
+                return;
+            };
+
+            let config = {
+                let guard = self.state.config.read().unwrap();
+                guard.clone()
+            };
+            let sync = member_sync(&config, &entry, Some(event.player.as_ref())).await;
+            send_member_sync(self.state.discord_bridge.as_ref(), sync);
+        })
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use pumpkin::plugin::{BoxFuture, Cancellable, EventHandler};
+use pumpkin::plugin::events::player::player_chat::PlayerChatEvent;
+use pumpkin::plugin::events::player::player_command_send::PlayerCommandSendEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::seen::{format_duration, unix_now};
+
+const MUTES_FILE_NAME: &str = "mutes.yml";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MuteEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    pub issuer: String,
+    pub reason: String,
+    pub issued_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+impl MuteEntry {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    pub fn remaining_label(&self, now: u64) -> String {
+        match self.expires_at {
+            Some(expires_at) => format!(
+                "for {}",
+                format_duration(Duration::from_secs(expires_at.saturating_sub(now)))
+            ),
+            None => "permanently".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MuteFile {
+    mutes: Vec<MuteEntry>,
+}
+
+pub type MuteMap = Arc<RwLock<HashMap<Uuid, MuteEntry>>>;
+
+pub fn mutes_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(MUTES_FILE_NAME)
+}
+
+pub fn load_mutes(path: &Path) -> Result<HashMap<Uuid, MuteEntry>, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file = serde_yaml::from_str::<MuteFile>(&content).map_err(|e| e.to_string())?;
+    Ok(file
+        .mutes
+        .into_iter()
+        .map(|entry| (entry.uuid, entry))
+        .collect())
+}
+
+pub fn save_mutes(path: &Path, mutes: &HashMap<Uuid, MuteEntry>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut entries = mutes.values().cloned().collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.issued_at);
+    let file = MuteFile { mutes: entries };
+    let content = serde_yaml::to_string(&file).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Returns the player's mute if it is still running, dropping it once it has expired.
+pub fn active_mute(mutes: &MuteMap, data_dir: &Path, uuid: Uuid) -> Option<MuteEntry> {
+    let now = unix_now();
+    let entry = mutes.read().unwrap().get(&uuid).cloned()?;
+    if !entry.is_expired(now) {
+        return Some(entry);
+    }
+
+    // Someone may have re-muted or unmuted the player since the read lock was released.
+    let mut guard = mutes.write().unwrap();
+    if !guard.get(&uuid).is_some_and(|entry| entry.is_expired(now)) {
+        return guard.get(&uuid).cloned();
+    }
+    guard.remove(&uuid);
+    if let Err(err) = save_mutes(&mutes_path(data_dir), &guard) {
+        log::warn!("Failed to save mutes.yml: {err}");
+    }
+    None
+}
+
+pub fn mute_notice(config: &Config, entry: &MuteEntry) -> TextComponent {
+    let text = config
+        .mute_message
+        .replace("{REMAINING}", &entry.remaining_label(unix_now()))
+        .replace("{REASON}", &entry.reason)
+        .replace("{ISSUER}", &entry.issuer);
+    TextComponent::text(text).color_named(NamedColor::Red)
+}
+
+pub struct MuteChatHandler {
+    config: Arc<RwLock<Config>>,
+    mutes: MuteMap,
+    data_dir: PathBuf,
+}
+
+impl MuteChatHandler {
+    pub fn new(config: Arc<RwLock<Config>>, mutes: MuteMap, data_dir: PathBuf) -> Self {
+        Self {
+            config,
+            mutes,
+            data_dir,
+        }
+    }
+}
+
+impl EventHandler<PlayerChatEvent> for MuteChatHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerChatEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let Some(entry) = active_mute(&self.mutes, &self.data_dir, event.player.gameprofile.id)
+            else {
+                return;
+            };
+
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+
+            event.set_cancelled(true);
+            event
+                .player
+                .send_system_message(&mute_notice(&config, &entry))
+                .await;
+        })
+    }
+}
+
+pub struct MuteCommandHandler {
+    config: Arc<RwLock<Config>>,
+    mutes: MuteMap,
+    data_dir: PathBuf,
+}
+
+impl MuteCommandHandler {
+    pub fn new(config: Arc<RwLock<Config>>, mutes: MuteMap, data_dir: PathBuf) -> Self {
+        Self {
+            config,
+            mutes,
+            data_dir,
+        }
+    }
+}
+
+pub fn command_root(command: &str) -> String {
+    let root = command
+        .trim_start_matches('/')
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match root.split_once(':') {
+        Some((_, name)) => name.to_string(),
+        None => root,
+    }
+}
+
+impl EventHandler<PlayerCommandSendEvent> for MuteCommandHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerCommandSendEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let config = {
+                let guard = self.config.read().unwrap();
+                guard.clone()
+            };
+
+            let root = command_root(&event.command);
+            if !config
+                .mute_blocked_commands
+                .iter()
+                .any(|blocked| blocked.eq_ignore_ascii_case(&root))
+            {
+                return;
+            }
+
+            let Some(entry) = active_mute(&self.mutes, &self.data_dir, event.player.gameprofile.id)
+            else {
+                return;
+            };
+
+            event.set_cancelled(true);
+            event
+                .player
+                .send_system_message(&mute_notice(&config, &entry))
+                .await;
+        })
+    }
+}
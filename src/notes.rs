@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::seen::{format_date, unix_now};
+
+pub const PERMISSION_NOTES: &str = "JinxCore:notes";
+
+const NOTES_FILE_NAME: &str = "notes.yml";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoteEntry {
+    pub id: u64,
+    pub uuid: Uuid,
+    pub name: String,
+    pub author: String,
+    pub text: String,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteStore {
+    pub next_id: u64,
+    pub notes: Vec<NoteEntry>,
+}
+
+impl Default for NoteStore {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            notes: Vec::new(),
+        }
+    }
+}
+
+impl NoteStore {
+    pub fn add(&mut self, uuid: Uuid, name: String, author: String, text: String) -> NoteEntry {
+        let entry = NoteEntry {
+            id: self.next_id,
+            uuid,
+            name,
+            author,
+            text,
+            created_at: unix_now(),
+        };
+        self.next_id += 1;
+        self.notes.push(entry.clone());
+        entry
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<NoteEntry> {
+        let index = self.notes.iter().position(|entry| entry.id == id)?;
+        Some(self.notes.remove(index))
+    }
+
+    pub fn for_player(&self, uuid: Uuid) -> Vec<NoteEntry> {
+        self.notes
+            .iter()
+            .filter(|entry| entry.uuid == uuid)
+            .cloned()
+            .collect()
+    }
+}
+
+pub fn notes_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(NOTES_FILE_NAME)
+}
+
+pub fn load_notes(path: &Path) -> Result<NoteStore, String> {
+    if !path.exists() {
+        return Ok(NoteStore::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let store = serde_yaml::from_str::<NoteStore>(&content).map_err(|e| e.to_string())?;
+    Ok(store)
+}
+
+pub fn save_notes(path: &Path, store: &NoteStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_yaml::to_string(store).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// One-line summary used by `/whois`, e.g. `Notes: 2 (latest #5 by Jinx on 2026-01-02: ...)`.
+pub fn notes_summary(store: &NoteStore, uuid: Uuid) -> String {
+    let notes = store.for_player(uuid);
+    match notes.last() {
+        Some(latest) => format!(
+            "Notes: {} (latest #{} by {} on {}: {})",
+            notes.len(),
+            latest.id,
+            latest.author,
+            format_date(latest.created_at),
+            latest.text
+        ),
+        None => "Notes: 0".to_string(),
+    }
+}
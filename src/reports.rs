@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::discord_bot::{DiscordBridge, DiscordRoute, escape_markdown, send_discord_message};
+use crate::seen::unix_now;
+use crate::staff::notify_staff;
+use crate::webhook::send_webhook_embed;
+use crate::branding;
+
+pub const PERMISSION_REPORTS: &str = "JinxCore:reports";
+
+const REPORTS_FILE_NAME: &str = "reports.yml";
+const REPORT_COLOR: u32 = 0xFEE75C;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    Open,
+    Claimed,
+    Closed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReportEntry {
+    pub id: u64,
+    pub reporter: String,
+    pub reporter_uuid: Uuid,
+    pub reporter_position: String,
+    pub target: String,
+    pub target_uuid: Option<Uuid>,
+    /// `None` when the target was offline at the time of the report.
+    pub target_position: Option<String>,
+    pub reason: String,
+    pub created_at: u64,
+    pub status: ReportStatus,
+    #[serde(default)]
+    pub claimed_by: Option<String>,
+    #[serde(default)]
+    pub closed_by: Option<String>,
+    #[serde(default)]
+    pub resolution: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStore {
+    pub next_id: u64,
+    pub reports: Vec<ReportEntry>,
+}
+
+impl Default for ReportStore {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            reports: Vec::new(),
+        }
+    }
+}
+
+impl ReportStore {
+    pub fn add(
+        &mut self,
+        reporter: &Player,
+        target: String,
+        target_player: Option<&Player>,
+        reason: String,
+    ) -> ReportEntry {
+        let entry = ReportEntry {
+            id: self.next_id,
+            reporter: reporter.gameprofile.name.clone(),
+            reporter_uuid: reporter.gameprofile.id,
+            reporter_position: describe_position(reporter),
+            target,
+            target_uuid: target_player.map(|player| player.gameprofile.id),
+            target_position: target_player.map(describe_position),
+            reason,
+            created_at: unix_now(),
+            status: ReportStatus::Open,
+            claimed_by: None,
+            closed_by: None,
+            resolution: None,
+        };
+        self.next_id += 1;
+        self.reports.push(entry.clone());
+        entry
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut ReportEntry> {
+        self.reports.iter_mut().find(|entry| entry.id == id)
+    }
+
+    /// Open and claimed reports, oldest first.
+    pub fn pending(&self) -> Vec<ReportEntry> {
+        self.reports
+            .iter()
+            .filter(|entry| entry.status != ReportStatus::Closed)
+            .cloned()
+            .collect()
+    }
+}
+
+fn describe_position(player: &Player) -> String {
+    let pos = player.position();
+    format!(
+        "{} {:.0} {:.0} {:.0}",
+        player.world().dimension.minecraft_name,
+        pos.x,
+        pos.y,
+        pos.z
+    )
+}
+
+pub fn reports_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(REPORTS_FILE_NAME)
+}
+
+pub fn load_reports(path: &Path) -> Result<ReportStore, String> {
+    if !path.exists() {
+        return Ok(ReportStore::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let store = serde_yaml::from_str::<ReportStore>(&content).map_err(|e| e.to_string())?;
+    Ok(store)
+}
+
+pub fn save_reports(path: &Path, store: &ReportStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_yaml::to_string(store).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Fills in the template, passing the player-supplied values through `escape`.
+fn format_report(template: &str, entry: &ReportEntry, escape: fn(&str) -> String) -> String {
+    template
+        .replace("{ID}", &entry.id.to_string())
+        .replace("{PLAYER}", &escape(&entry.reporter))
+        .replace("{TARGET}", &escape(&entry.target))
+        .replace("{REASON}", &escape(&entry.reason))
+}
+
+/// Tells online staff about a new report and forwards it to Discord and the webhook.
+pub async fn announce_report(
+    server: &Server,
+    config: &Config,
+    discord: Option<&DiscordBridge>,
+    entry: &ReportEntry,
+) {
+    let line = format_report(&config.report_format, entry, str::to_string);
+    let message = branding::brand(
+        TextComponent::text(format!("{line} (/reports claim {})", entry.id))
+            .color_named(NamedColor::Gold),
+    );
+    notify_staff(server, PERMISSION_REPORTS, &message).await;
+
+    // Both Discord paths render markdown, so names and the reason are escaped there.
+    let discord_line = format_report(&config.report_format, entry, escape_markdown);
+    if config.report_send_discord {
+        send_discord_message(discord, config, DiscordRoute::StaffAlert, discord_line.clone());
+    }
+    if config.report_send_webhook {
+        send_webhook_embed(config, discord_line, REPORT_COLOR);
+    }
+}
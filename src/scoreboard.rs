@@ -104,6 +104,66 @@ fn write_text_component(
     })
 }
 
+/// Values for the `%...%` placeholders, shared by the sidebar and the Discord status.
+pub struct Placeholders {
+    online: usize,
+    max_players: String,
+    tps: f64,
+    uptime: String,
+    chat_status: &'static str,
+    chat_slow: String,
+}
+
+impl Placeholders {
+    pub async fn collect(server: &Server, state: &PluginState) -> Self {
+        let players = collect_players(server).await;
+        let online = {
+            let vanished = state.vanished.read().unwrap();
+            players
+                .iter()
+                .filter(|player| !vanished.contains(&player.gameprofile.id))
+                .count()
+        };
+        let max = server.basic_config.max_players;
+        let max_players = if max == 0 {
+            "inf".to_string()
+        } else {
+            max.to_string()
+        };
+
+        let avg_nanos = server.get_average_tick_time_nanos();
+        let tps = if avg_nanos <= 0 {
+            0.0
+        } else {
+            let target = server.tick_rate_manager.tickrate() as f64;
+            let current = 1_000_000_000.0 / avg_nanos as f64;
+            current.min(target)
+        };
+        let (chat_status, chat_slow) = {
+            let control = state.chat_control.read().unwrap();
+            (control.status_label(), control.slow_label())
+        };
+
+        Self {
+            online,
+            max_players,
+            tps,
+            uptime: format_uptime(state.start_time.elapsed().as_secs()),
+            chat_status,
+            chat_slow,
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        text.replace("%online%", &self.online.to_string())
+            .replace("%max%", &self.max_players)
+            .replace("%tps%", &format!("{:.2}", self.tps))
+            .replace("%uptime%", &self.uptime)
+            .replace("%chat_status%", self.chat_status)
+            .replace("%chat_slow%", &self.chat_slow)
+    }
+}
+
 pub fn start_scoreboard_task(server: Arc<Server>, state: Arc<PluginState>) {
     let server_ref = Arc::clone(&server);
     thread::spawn(move || {
@@ -147,27 +207,14 @@ async fn update_sidebar(server: &Server, state: &PluginState, config: &Config) {
         initialized.retain(|id| current_ids.contains(id));
     }
 
-    let avg_nanos = server.get_average_tick_time_nanos();
-    let tps = if avg_nanos <= 0 {
-        0.0
-    } else {
-        let target = server.tick_rate_manager.tickrate() as f64;
-        let current = 1_000_000_000.0 / avg_nanos as f64;
-        current.min(target)
-    };
-    let uptime = format_uptime(state.start_time.elapsed().as_secs());
-
+    let placeholders = Placeholders::collect(server, state).await;
     let (title_text, raw_lines) =
         load_scoreboard_text(&state.data_dir, &config.scoreboard_title);
     let title = parse_colored_text(&title_text);
 
     let rendered_lines = raw_lines
         .into_iter()
-        .map(|line| {
-            line.replace("%online%", &players.len().to_string())
-                .replace("%tps%", &format!("{tps:.2}"))
-                .replace("%uptime%", &uptime)
-        })
+        .map(|line| placeholders.apply(&line))
         .collect::<Vec<_>>();
 
     let mut lines = Vec::new();
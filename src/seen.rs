@@ -1,15 +1,36 @@
 use std::collections::HashMap;
-use std::time::{Duration, SystemTime};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use pumpkin::SHOULD_STOP;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug)]
+use crate::PluginState;
+
+const SEEN_FILE_NAME: &str = "seen.yml";
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SeenEntry {
     pub uuid: Uuid,
     pub name: String,
     pub last_seen: SystemTime,
     pub online: bool,
     pub last_address: Option<String>,
+    #[serde(default)]
+    pub addresses: Vec<AddressRecord>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AddressRecord {
+    pub address: String,
+    pub first_seen: u64,
+    pub last_seen: u64,
 }
 
 impl SeenEntry {
@@ -20,8 +41,92 @@ impl SeenEntry {
             last_seen: SystemTime::now(),
             online: true,
             last_address: address,
+            addresses: Vec::new(),
+        }
+    }
+
+    fn record_address(&mut self, address: &str) {
+        let now = unix_now();
+        match self
+            .addresses
+            .iter_mut()
+            .find(|record| record.address == address)
+        {
+            Some(record) => record.last_seen = now,
+            None => self.addresses.push(AddressRecord {
+                address: address.to_string(),
+                first_seen: now,
+                last_seen: now,
+            }),
         }
     }
+
+    pub fn has_address(&self, address: &str) -> bool {
+        self.last_address.as_deref() == Some(address)
+            || self.addresses.iter().any(|record| record.address == address)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SeenFile {
+    players: Vec<SeenEntry>,
+}
+
+pub fn seen_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(SEEN_FILE_NAME)
+}
+
+pub fn load_seen(path: &Path) -> Result<HashMap<Uuid, SeenEntry>, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file = serde_yaml::from_str::<SeenFile>(&content).map_err(|e| e.to_string())?;
+    Ok(file
+        .players
+        .into_iter()
+        .map(|mut entry| {
+            entry.online = false;
+            (entry.uuid, entry)
+        })
+        .collect())
+}
+
+pub fn save_seen(path: &Path, seen: &HashMap<Uuid, SeenEntry>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut players = seen.values().cloned().collect::<Vec<_>>();
+    players.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    let file = SeenFile { players };
+    let content = serde_yaml::to_string(&file).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Writes seen.yml if a join or leave changed it since the last write.
+pub fn flush_seen(state: &PluginState) {
+    if !state.seen_dirty.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    let seen = state.seen.read().unwrap().clone();
+    if let Err(err) = save_seen(&seen_path(&state.data_dir), &seen) {
+        log::warn!("Failed to save seen.yml: {err}");
+        state.seen_dirty.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Saves pending seen updates in the background so joins and leaves never wait on the disk.
+pub fn start_seen_save_task(state: Arc<PluginState>) {
+    thread::spawn(move || {
+        loop {
+            if SHOULD_STOP.load(Ordering::Relaxed) {
+                break;
+            }
+            flush_seen(&state);
+            thread::sleep(SAVE_INTERVAL);
+        }
+    });
 }
 
 pub fn update_on_join(
@@ -36,6 +141,9 @@ pub fn update_on_join(
     entry.name = name;
     entry.online = true;
     entry.last_seen = SystemTime::now();
+    if let Some(address) = &address {
+        entry.record_address(address);
+    }
     entry.last_address = address;
 }
 
@@ -74,3 +182,56 @@ pub fn format_duration(duration: Duration) -> String {
         format!("{seconds}s")
     }
 }
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Parses compact durations such as `30m`, `7d12h` or `1w`.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let mut total_secs: u64 = 0;
+    let mut number = String::new();
+    let mut parsed_unit = false;
+
+    for ch in input.trim().to_lowercase().chars() {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+        let unit_secs = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => 86_400,
+            'w' => 604_800,
+            _ => return None,
+        };
+        let value = number.parse::<u64>().ok()?;
+        total_secs = total_secs.checked_add(value.checked_mul(unit_secs)?)?;
+        number.clear();
+        parsed_unit = true;
+    }
+
+    if !number.is_empty() || !parsed_unit || total_secs == 0 {
+        return None;
+    }
+    Some(Duration::from_secs(total_secs))
+}
+
+/// Formats a unix timestamp as a `YYYY-MM-DD` UTC date.
+pub fn format_date(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,339 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use pumpkin::entity::player::Player;
+use pumpkin::plugin::{BoxFuture, EventHandler};
+use pumpkin::plugin::events::player::player_death::PlayerDeathEvent;
+use pumpkin::plugin::events::player::player_leave::PlayerLeaveEvent;
+use pumpkin::server::Server;
+use pumpkin_data::data_component_impl::EquipmentSlot;
+use pumpkin_data::item::Item;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::{read_gzip_compound_tag, write_gzip_compound_tag};
+use pumpkin_world::item::ItemStack;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::PluginState;
+use crate::seen::unix_now;
+
+const SNAPSHOTS_DIR_NAME: &str = "snapshots";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotReason {
+    Clearinv,
+    Kit,
+    Death,
+    Logout,
+    Restore,
+}
+
+impl SnapshotReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Clearinv => "clearinv",
+            Self::Kit => "kit",
+            Self::Death => "death",
+            Self::Logout => "logout",
+            Self::Restore => "before restore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotItem {
+    pub slot: usize,
+    pub id: String,
+    pub count: u8,
+    /// The whole stack with its components, as hex-encoded NBT. Older snapshots lack it and
+    /// restore from `id` and `count`.
+    #[serde(default)]
+    pub nbt: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotEquipment {
+    pub slot: String,
+    pub id: String,
+    pub count: u8,
+    #[serde(default)]
+    pub nbt: Option<String>,
+}
+
+/// Unlike kits, snapshots keep item components (enchantments, names, damage and so on), so a
+/// restore gives back the exact stacks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventorySnapshot {
+    pub taken_at: u64,
+    pub reason: SnapshotReason,
+    pub items: Vec<SnapshotItem>,
+    pub equipment: Vec<SnapshotEquipment>,
+}
+
+impl InventorySnapshot {
+    pub fn item_count(&self) -> u64 {
+        self.items
+            .iter()
+            .map(|item| u64::from(item.count))
+            .chain(self.equipment.iter().map(|item| u64::from(item.count)))
+            .sum()
+    }
+}
+
+fn equipment_slot_name(slot: &EquipmentSlot) -> &'static str {
+    match slot {
+        EquipmentSlot::MainHand(_) => "mainhand",
+        EquipmentSlot::OffHand(_) => "offhand",
+        EquipmentSlot::Feet(_) => "feet",
+        EquipmentSlot::Legs(_) => "legs",
+        EquipmentSlot::Chest(_) => "chest",
+        EquipmentSlot::Head(_) => "head",
+        EquipmentSlot::Body(_) => "body",
+        EquipmentSlot::Saddle(_) => "saddle",
+    }
+}
+
+fn equipment_slot_from_name(name: &str) -> Option<EquipmentSlot> {
+    match name.to_ascii_lowercase().as_str() {
+        "mainhand" => Some(EquipmentSlot::MAIN_HAND),
+        "offhand" => Some(EquipmentSlot::OFF_HAND),
+        "feet" => Some(EquipmentSlot::FEET),
+        "legs" => Some(EquipmentSlot::LEGS),
+        "chest" => Some(EquipmentSlot::CHEST),
+        "head" => Some(EquipmentSlot::HEAD),
+        "body" => Some(EquipmentSlot::BODY),
+        "saddle" => Some(EquipmentSlot::SADDLE),
+        _ => None,
+    }
+}
+
+fn item_from_id(id: &str, count: u8) -> Option<ItemStack> {
+    let registry_key = id.strip_prefix("minecraft:").unwrap_or(id);
+    let item = Item::from_registry_key(registry_key)?;
+    Some(ItemStack::new(count, item))
+}
+
+fn encode_stack(stack: &ItemStack) -> Option<String> {
+    let mut compound = NbtCompound::new();
+    stack.write_item_stack(&mut compound);
+    let mut bytes = Vec::new();
+    if let Err(err) = write_gzip_compound_tag(&compound, &mut bytes) {
+        log::warn!("Failed to encode minecraft:{}: {err}", stack.item.registry_key);
+        return None;
+    }
+    Some(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn decode_stack(nbt: &str) -> Option<ItemStack> {
+    if nbt.len() % 2 != 0 {
+        return None;
+    }
+    let bytes = (0..nbt.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(nbt.get(index..index + 2)?, 16).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let compound = read_gzip_compound_tag(bytes.as_slice()).ok()?;
+    ItemStack::read_item_stack(&compound)
+}
+
+/// Rebuilds a stored stack, from its NBT when there is any.
+fn stored_stack(id: &str, count: u8, nbt: Option<&str>) -> Option<ItemStack> {
+    match nbt {
+        Some(nbt) => decode_stack(nbt),
+        None => item_from_id(id, count),
+    }
+}
+
+fn snapshot_path(data_dir: &Path, uuid: Uuid) -> PathBuf {
+    data_dir.join(SNAPSHOTS_DIR_NAME).join(format!("{uuid}.yml"))
+}
+
+/// Snapshots for one player, oldest first.
+pub fn load_snapshots(data_dir: &Path, uuid: Uuid) -> Result<Vec<InventorySnapshot>, String> {
+    let path = snapshot_path(data_dir, uuid);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let snapshots =
+        serde_yaml::from_str::<Vec<InventorySnapshot>>(&content).map_err(|e| e.to_string())?;
+    Ok(snapshots)
+}
+
+fn save_snapshots(
+    data_dir: &Path,
+    uuid: Uuid,
+    snapshots: &[InventorySnapshot],
+) -> Result<(), String> {
+    let path = snapshot_path(data_dir, uuid);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_yaml::to_string(snapshots).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+async fn capture(player: &Player, reason: SnapshotReason) -> InventorySnapshot {
+    let inventory = player.inventory();
+    let mut items = Vec::new();
+    for (index, slot) in inventory.main_inventory.iter().enumerate() {
+        let slot_lock = slot.lock().await;
+        if slot_lock.is_empty() {
+            continue;
+        }
+        items.push(SnapshotItem {
+            slot: index,
+            id: format!("minecraft:{}", slot_lock.item.registry_key),
+            count: slot_lock.item_count,
+            nbt: encode_stack(&slot_lock),
+        });
+    }
+
+    let mut equipment = Vec::new();
+    let equipment_lock = inventory.entity_equipment.lock().await;
+    for (slot, stack) in equipment_lock.equipment.iter() {
+        let stack_lock = stack.lock().await;
+        if stack_lock.is_empty() {
+            continue;
+        }
+        equipment.push(SnapshotEquipment {
+            slot: equipment_slot_name(slot).to_string(),
+            id: format!("minecraft:{}", stack_lock.item.registry_key),
+            count: stack_lock.item_count,
+            nbt: encode_stack(&stack_lock),
+        });
+    }
+
+    InventorySnapshot {
+        taken_at: unix_now(),
+        reason,
+        items,
+        equipment,
+    }
+}
+
+/// Stores the player's current inventory, keeping at most `limit` snapshots.
+/// Empty inventories are not recorded.
+pub async fn take_snapshot(
+    player: &Player,
+    reason: SnapshotReason,
+    data_dir: &Path,
+    limit: usize,
+) {
+    if limit == 0 {
+        return;
+    }
+    let snapshot = capture(player, reason).await;
+    if snapshot.items.is_empty() && snapshot.equipment.is_empty() {
+        return;
+    }
+
+    let uuid = player.gameprofile.id;
+    let mut snapshots = load_snapshots(data_dir, uuid).unwrap_or_else(|err| {
+        log::warn!("Failed to read snapshots/{uuid}.yml: {err}");
+        Vec::new()
+    });
+    snapshots.push(snapshot);
+    if snapshots.len() > limit {
+        let excess = snapshots.len() - limit;
+        snapshots.drain(..excess);
+    }
+    if let Err(err) = save_snapshots(data_dir, uuid, &snapshots) {
+        log::warn!("Failed to save snapshots/{uuid}.yml: {err}");
+    }
+}
+
+/// Replaces the player's inventory and equipment with the snapshot.
+/// Returns the number of stacks that could not be restored.
+pub async fn restore_snapshot(player: &Player, snapshot: &InventorySnapshot) -> usize {
+    let inventory = player.inventory();
+    let mut skipped = 0usize;
+
+    for slot in &inventory.main_inventory {
+        *slot.lock().await = ItemStack::EMPTY.clone();
+    }
+    for entry in &snapshot.items {
+        if entry.slot >= inventory.main_inventory.len() {
+            skipped += 1;
+            continue;
+        }
+        let Some(stack) = stored_stack(&entry.id, entry.count, entry.nbt.as_deref()) else {
+            skipped += 1;
+            continue;
+        };
+        *inventory.main_inventory[entry.slot].lock().await = stack;
+    }
+
+    let mut equipment = inventory.entity_equipment.lock().await;
+    for stack in equipment.equipment.values() {
+        *stack.lock().await = ItemStack::EMPTY.clone();
+    }
+    for entry in &snapshot.equipment {
+        let Some(slot) = equipment_slot_from_name(&entry.slot) else {
+            skipped += 1;
+            continue;
+        };
+        let Some(stack) = stored_stack(&entry.id, entry.count, entry.nbt.as_deref()) else {
+            skipped += 1;
+            continue;
+        };
+        equipment.put(&slot, stack).await;
+    }
+
+    skipped
+}
+
+pub async fn snapshot_for_state(player: &Player, reason: SnapshotReason, state: &PluginState) {
+    let limit = state.config.read().unwrap().inventory_snapshot_limit;
+    take_snapshot(player, reason, &state.data_dir, limit).await;
+}
+
+pub struct SnapshotDeathHandler {
+    state: Arc<PluginState>,
+}
+
+impl SnapshotDeathHandler {
+    pub fn new(state: Arc<PluginState>) -> Self {
+        Self { state }
+    }
+}
+
+impl EventHandler<PlayerDeathEvent> for SnapshotDeathHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerDeathEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            snapshot_for_state(&event.player, SnapshotReason::Death, &self.state).await;
+        })
+    }
+}
+
+pub struct SnapshotLeaveHandler {
+    state: Arc<PluginState>,
+}
+
+impl SnapshotLeaveHandler {
+    pub fn new(state: Arc<PluginState>) -> Self {
+        Self { state }
+    }
+}
+
+impl EventHandler<PlayerLeaveEvent> for SnapshotLeaveHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerLeaveEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if self.state.join_flood.read().unwrap().is_kicked(event.player.gameprofile.id) {
+                return;
+            }
+            snapshot_for_state(&event.player, SnapshotReason::Logout, &self.state).await;
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use pumpkin::server::Server;
+use pumpkin_util::text::TextComponent;
+
+/// Sends a message to every online player holding the given permission.
+pub async fn notify_staff(server: &Server, permission: &str, message: &TextComponent) {
+    for world in server.worlds.read().await.iter() {
+        let players = world.players.read().await.values().cloned().collect::<Vec<_>>();
+        for player in players {
+            if player.has_permission(permission).await {
+                player.send_system_message(message).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use pumpkin::command::CommandSender;
+use pumpkin::entity::player::Player;
+use pumpkin::plugin::{BoxFuture, EventHandler};
+use pumpkin::plugin::events::player::player_change_world::PlayerChangeWorldEvent;
+use pumpkin::plugin::events::player::player_join::PlayerJoinEvent;
+use pumpkin::plugin::events::player::player_teleport::PlayerTeleportEvent;
+use pumpkin::server::Server;
+use pumpkin_data::entity::EntityType;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::client::play::{
+    CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo, CSpawnEntity, PlayerAction,
+    PlayerInfoFlags,
+};
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::branding;
+use crate::joinflood::JoinFloodState;
+
+pub const PERMISSION_SEE_VANISHED: &str = "JinxCore:see-vanished";
+
+const VANISH_FILE_NAME: &str = "vanished.yml";
+/// One server tick, by which the packets of the event being handled have been sent.
+const EVENT_SETTLE_DELAY: Duration = Duration::from_millis(50);
+
+pub type VanishSet = Arc<RwLock<HashSet<Uuid>>>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct VanishFile {
+    players: Vec<Uuid>,
+}
+
+pub fn vanish_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(VANISH_FILE_NAME)
+}
+
+pub fn load_vanished(path: &Path) -> Result<HashSet<Uuid>, String> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file = serde_yaml::from_str::<VanishFile>(&content).map_err(|e| e.to_string())?;
+    Ok(file.players.into_iter().collect())
+}
+
+pub fn save_vanished(path: &Path, vanished: &HashSet<Uuid>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut players = vanished.iter().copied().collect::<Vec<_>>();
+    players.sort();
+    let file = VanishFile { players };
+    let content = serde_yaml::to_string(&file).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn is_vanished(vanished: &VanishSet, uuid: Uuid) -> bool {
+    vanished.read().unwrap().contains(&uuid)
+}
+
+/// Console always sees vanished players; players need the `see-vanished` permission.
+pub async fn sees_vanished(sender: &CommandSender) -> bool {
+    match sender.as_player() {
+        Some(player) => player.has_permission(PERMISSION_SEE_VANISHED).await,
+        None => true,
+    }
+}
+
+/// Drops vanished players from `players` unless `sender` may see them.
+pub async fn filter_visible(
+    sender: &CommandSender,
+    vanished: &VanishSet,
+    players: &mut Vec<Arc<Player>>,
+) {
+    if sees_vanished(sender).await {
+        return;
+    }
+    let vanished = vanished.read().unwrap();
+    players.retain(|player| !vanished.contains(&player.gameprofile.id));
+}
+
+pub async fn hide_from(viewer: &Player, target: &Player) {
+    let uuids = [target.gameprofile.id];
+    viewer.client.enqueue_packet(&CRemovePlayerInfo::new(&uuids)).await;
+    let entity_ids = [VarInt(target.entity_id())];
+    viewer.client.enqueue_packet(&CRemoveEntities::new(&entity_ids)).await;
+}
+
+pub async fn show_to(viewer: &Player, target: &Player) {
+    let profile = &target.gameprofile;
+    let actions = [
+        PlayerAction::AddPlayer {
+            name: &profile.name,
+            properties: &profile.properties,
+        },
+        PlayerAction::UpdateListed(true),
+    ];
+    let info = CPlayerInfoUpdate::new(
+        (PlayerInfoFlags::ADD_PLAYER | PlayerInfoFlags::UPDATE_LISTED).bits(),
+        &[pumpkin_protocol::java::client::play::Player {
+            uuid: profile.id,
+            actions: &actions,
+        }],
+    );
+    viewer.client.enqueue_packet(&info).await;
+
+    if !Arc::ptr_eq(&viewer.living_entity.entity.world, &target.living_entity.entity.world) {
+        return;
+    }
+    let entity = &target.living_entity.entity;
+    let yaw = entity.yaw.load();
+    let spawn = CSpawnEntity::new(
+        VarInt(target.entity_id()),
+        profile.id,
+        VarInt(i32::from(EntityType::PLAYER.id)),
+        target.position(),
+        entity.pitch.load(),
+        yaw,
+        entity.head_yaw.load(),
+        VarInt(0),
+        Vector3::new(0.0, 0.0, 0.0),
+    );
+    viewer.client.enqueue_packet(&spawn).await;
+}
+
+/// Hides or reveals `target` for every online player without `see-vanished`.
+pub async fn apply_visibility(server: &Server, target: &Player, hidden: bool) {
+    for world in server.worlds.read().await.iter() {
+        let viewers = world.players.read().await.values().cloned().collect::<Vec<_>>();
+        for viewer in viewers {
+            if viewer.gameprofile.id == target.gameprofile.id
+                || viewer.has_permission(PERMISSION_SEE_VANISHED).await
+            {
+                continue;
+            }
+            if hidden {
+                hide_from(&viewer, target).await;
+            } else {
+                show_to(&viewer, target).await;
+            }
+        }
+    }
+}
+
+/// Hides `player` from everyone if they are vanished, and every vanished player from `player`
+/// unless they may see them.
+pub async fn hide_around(server: &Server, vanished: &VanishSet, player: &Player) {
+    if is_vanished(vanished, player.gameprofile.id) {
+        apply_visibility(server, player, true).await;
+    }
+    if player.has_permission(PERMISSION_SEE_VANISHED).await {
+        return;
+    }
+    let hidden = vanished.read().unwrap().clone();
+    if hidden.is_empty() {
+        return;
+    }
+    for world in server.worlds.read().await.iter() {
+        let others = world.players.read().await.values().cloned().collect::<Vec<_>>();
+        for other in others {
+            if other.gameprofile.id != player.gameprofile.id
+                && hidden.contains(&other.gameprofile.id)
+            {
+                hide_from(player, &other).await;
+            }
+        }
+    }
+}
+
+/// Runs `hide_around` once the server has finished the action an event announces. Handlers
+/// run before the server sends the new spawn and tab list packets, so hiding right away would
+/// be undone by them.
+fn hide_after_event(server: &Arc<Server>, vanished: &VanishSet, player: &Arc<Player>) {
+    let server = Arc::clone(server);
+    let vanished = Arc::clone(vanished);
+    let player = Arc::clone(player);
+    tokio::spawn(async move {
+        tokio::time::sleep(EVENT_SETTLE_DELAY).await;
+        hide_around(&server, &vanished, &player).await;
+    });
+}
+
+/// Hides vanished players from the joining player and the joining player from everyone if they
+/// are vanished.
+pub struct VanishJoinHandler {
+    vanished: VanishSet,
+    join_flood: JoinFloodState,
+}
+
+impl VanishJoinHandler {
+    pub fn new(vanished: VanishSet, join_flood: JoinFloodState) -> Self {
+        Self {
+            vanished,
+            join_flood,
+        }
+    }
+}
+
+impl EventHandler<PlayerJoinEvent> for VanishJoinHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        server: &'a Arc<Server>,
+        event: &'a mut PlayerJoinEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let player = &event.player;
+            if self.join_flood.read().unwrap().is_kicked(player.gameprofile.id) {
+                return;
+            }
+            if is_vanished(&self.vanished, player.gameprofile.id) {
+                let msg = branding::brand(
+                    TextComponent::text("You are still vanished.").color_named(NamedColor::Gray),
+                );
+                player.send_system_message(&msg).await;
+            }
+            hide_around(server, &self.vanished, player).await;
+            hide_after_event(server, &self.vanished, player);
+        })
+    }
+}
+
+/// Teleports, respawns and world changes make the server spawn the player for everyone nearby
+/// and everyone nearby for the player again, so the hides are redone after each.
+pub struct VanishRespawnHandler {
+    vanished: VanishSet,
+}
+
+impl VanishRespawnHandler {
+    pub fn new(vanished: VanishSet) -> Self {
+        Self { vanished }
+    }
+}
+
+impl EventHandler<PlayerTeleportEvent> for VanishRespawnHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        server: &'a Arc<Server>,
+        event: &'a mut PlayerTeleportEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            hide_after_event(server, &self.vanished, &event.player);
+        })
+    }
+}
+
+impl EventHandler<PlayerChangeWorldEvent> for VanishRespawnHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        server: &'a Arc<Server>,
+        event: &'a mut PlayerChangeWorldEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            hide_after_event(server, &self.vanished, &event.player);
+        })
+    }
+}
@@ -0,0 +1,223 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use pumpkin::command::CommandSender;
+use pumpkin::plugin::{BoxFuture, EventHandler};
+use pumpkin::plugin::events::player::player_join::PlayerJoinEvent;
+use pumpkin::server::Server;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::branding;
+use crate::config::Config;
+use crate::joinflood::JoinFloodState;
+use crate::seen::unix_now;
+
+const WARNINGS_FILE_NAME: &str = "warnings.yml";
+const SECS_PER_DAY: u64 = 86_400;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WarningEntry {
+    pub id: u64,
+    pub uuid: Uuid,
+    pub name: String,
+    pub issuer: String,
+    pub reason: String,
+    pub issued_at: u64,
+    pub delivered: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarningStore {
+    pub next_id: u64,
+    pub warnings: Vec<WarningEntry>,
+}
+
+impl Default for WarningStore {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl WarningStore {
+    pub fn add(
+        &mut self,
+        uuid: Uuid,
+        name: String,
+        issuer: String,
+        reason: String,
+    ) -> WarningEntry {
+        let entry = WarningEntry {
+            id: self.next_id,
+            uuid,
+            name,
+            issuer,
+            reason,
+            issued_at: unix_now(),
+            delivered: false,
+        };
+        self.next_id += 1;
+        self.warnings.push(entry.clone());
+        entry
+    }
+
+    pub fn for_player(&self, uuid: Uuid) -> Vec<WarningEntry> {
+        self.warnings
+            .iter()
+            .filter(|entry| entry.uuid == uuid)
+            .cloned()
+            .collect()
+    }
+
+    pub fn count_since(&self, uuid: Uuid, since: u64) -> usize {
+        self.warnings
+            .iter()
+            .filter(|entry| entry.uuid == uuid && entry.issued_at >= since)
+            .count()
+    }
+}
+
+pub fn warnings_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(WARNINGS_FILE_NAME)
+}
+
+pub fn load_warnings(path: &Path) -> Result<WarningStore, String> {
+    if !path.exists() {
+        return Ok(WarningStore::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let store = serde_yaml::from_str::<WarningStore>(&content).map_err(|e| e.to_string())?;
+    Ok(store)
+}
+
+pub fn save_warnings(path: &Path, store: &WarningStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_yaml::to_string(store).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn warning_notice(entry: &WarningEntry) -> TextComponent {
+    branding::brand(
+        TextComponent::text(format!(
+            "You have been warned by {}: {}",
+            entry.issuer, entry.reason
+        ))
+        .color_named(NamedColor::Red),
+    )
+}
+
+/// Runs the commands of every threshold that the player's latest warning has just reached.
+pub async fn apply_thresholds(
+    server: &Server,
+    config: &Config,
+    store: &Arc<RwLock<WarningStore>>,
+    uuid: Uuid,
+    name: &str,
+) {
+    let now = unix_now();
+    let mut commands = Vec::new();
+    {
+        let store = store.read().unwrap();
+        for threshold in &config.warn_thresholds {
+            if threshold.count == 0 {
+                continue;
+            }
+            let since = now.saturating_sub(threshold.within_days * SECS_PER_DAY);
+            if store.count_since(uuid, since) == threshold.count {
+                commands.extend(threshold.commands.iter().map(|command| {
+                    command
+                        .replace("{PLAYER}", name)
+                        .replace("{COUNT}", &threshold.count.to_string())
+                }));
+            }
+        }
+    }
+
+    if commands.is_empty() {
+        return;
+    }
+
+    let dispatcher = server.command_dispatcher.read().await;
+    for command in commands {
+        let command = command.trim_start_matches('/');
+        log::info!("Warning threshold reached for {name}, running: {command}");
+        dispatcher
+            .handle_command(&CommandSender::Console, server, command)
+            .await;
+    }
+}
+
+pub struct WarningJoinHandler {
+    warnings: Arc<RwLock<WarningStore>>,
+    data_dir: PathBuf,
+    join_flood: JoinFloodState,
+}
+
+impl WarningJoinHandler {
+    pub fn new(
+        warnings: Arc<RwLock<WarningStore>>,
+        data_dir: PathBuf,
+        join_flood: JoinFloodState,
+    ) -> Self {
+        Self {
+            warnings,
+            data_dir,
+            join_flood,
+        }
+    }
+}
+
+impl EventHandler<PlayerJoinEvent> for WarningJoinHandler {
+    fn handle_blocking<'a>(
+        &'a self,
+        _server: &'a Arc<Server>,
+        event: &'a mut PlayerJoinEvent,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let uuid = event.player.gameprofile.id;
+            if self.join_flood.read().unwrap().is_kicked(uuid) {
+                return;
+            }
+            let pending = {
+                let mut store = self.warnings.write().unwrap();
+                let mut pending = Vec::new();
+                for entry in store.warnings.iter_mut() {
+                    if entry.uuid == uuid && !entry.delivered {
+                        entry.delivered = true;
+                        pending.push(entry.clone());
+                    }
+                }
+                if !pending.is_empty() {
+                    if let Err(err) = save_warnings(&warnings_path(&self.data_dir), &store) {
+                        log::warn!("Failed to save warnings.yml: {err}");
+                    }
+                }
+                pending
+            };
+
+            if pending.is_empty() {
+                return;
+            }
+
+            let header = branding::brand(
+                TextComponent::text(format!(
+                    "You received {} warning(s) while you were offline:",
+                    pending.len()
+                ))
+                .color_named(NamedColor::Yellow),
+            );
+            event.player.send_system_message(&header).await;
+            for entry in &pending {
+                event.player.send_system_message(&warning_notice(entry)).await;
+            }
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::OnceLock;
-use std::thread;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 use reqwest::blocking::Client;
 use serde::Serialize;
@@ -8,11 +9,20 @@ use crate::config::Config;
 
 static CLIENT: OnceLock<Client> = OnceLock::new();
 
-#[derive(Clone, Copy)]
+/// Shared with the bot so deaths look the same in both relays.
+pub const DEATH_COLOR: u32 = 0x992D22;
+const SERVER_COLOR: u32 = 0x5865F2;
+/// Keeps a hanging webhook from holding up shutdown, when delivery is waited for.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone)]
 pub enum WebhookEvent {
     Chat,
     Join,
     Leave,
+    Death { killer: Option<String> },
+    /// Server start/stop notices; the message is sent as-is, without a player name.
+    Server,
 }
 
 #[derive(Serialize)]
@@ -42,31 +52,46 @@ fn format_message(template: &str, player: &str, message: Option<&str>) -> String
     output
 }
 
-pub fn send_webhook(config: &Config, event: WebhookEvent, player: &str, message: Option<&str>) {
+/// Posts the event in the background. The returned handle can be joined to wait for delivery,
+/// e.g. on shutdown.
+pub fn send_webhook(
+    config: &Config,
+    event: WebhookEvent,
+    player: &str,
+    message: Option<&str>,
+) -> Option<JoinHandle<()>> {
     if !config.webhook_enabled {
-        return;
+        return None;
     }
     if config.webhook_url.trim().is_empty() {
-        return;
+        return None;
     }
 
-    let (enabled, template) = match event {
+    let (enabled, template) = match &event {
         WebhookEvent::Chat => (config.webhook_send_chat, config.webhook_chat_format.as_str()),
         WebhookEvent::Join => (config.webhook_send_join, config.webhook_join_format.as_str()),
         WebhookEvent::Leave => (config.webhook_send_leave, config.webhook_leave_format.as_str()),
+        WebhookEvent::Death { .. } => {
+            (config.webhook_send_death, config.webhook_death_format.as_str())
+        }
+        WebhookEvent::Server => (config.webhook_send_lifecycle, "{MESSAGE}"),
     };
     if !enabled {
-        return;
+        return None;
     }
 
-    let formatted = format_message(template, player, message);
+    let mut formatted = format_message(template, player, message);
+    if let WebhookEvent::Death { killer } = &event {
+        formatted = formatted.replace("{KILLER}", killer.as_deref().unwrap_or_default());
+    }
     let url = config.webhook_url.clone();
-    let username = if config.webhook_use_player_name {
+    let is_server = matches!(event, WebhookEvent::Server);
+    let username = if config.webhook_use_player_name && !is_server {
         Some(player.to_string())
     } else {
         None
     };
-    let avatar_url = if config.webhook_avatar_url.trim().is_empty() {
+    let avatar_url = if config.webhook_avatar_url.trim().is_empty() || is_server {
         None
     } else {
         Some(
@@ -102,6 +127,49 @@ pub fn send_webhook(config: &Config, event: WebhookEvent, player: &str, message:
                 color: Some(0xED4245),
             }]),
         },
+        WebhookEvent::Death { .. } => WebhookPayload {
+            content: None,
+            username,
+            avatar_url,
+            embeds: Some(vec![WebhookEmbed {
+                description: formatted,
+                color: Some(DEATH_COLOR),
+            }]),
+        },
+        WebhookEvent::Server => WebhookPayload {
+            content: None,
+            username,
+            avatar_url,
+            embeds: Some(vec![WebhookEmbed {
+                description: formatted,
+                color: Some(SERVER_COLOR),
+            }]),
+        },
+    };
+    let client = CLIENT.get_or_init(Client::new).clone();
+    Some(thread::spawn(move || {
+        let _ = client.post(url).timeout(REQUEST_TIMEOUT).json(&payload).send();
+    }))
+}
+
+/// Posts a standalone embed, e.g. a player report, independent of the chat/join/leave toggles.
+pub fn send_webhook_embed(config: &Config, description: String, color: u32) {
+    if !config.webhook_enabled {
+        return;
+    }
+    if config.webhook_url.trim().is_empty() {
+        return;
+    }
+
+    let url = config.webhook_url.clone();
+    let payload = WebhookPayload {
+        content: None,
+        username: None,
+        avatar_url: None,
+        embeds: Some(vec![WebhookEmbed {
+            description,
+            color: Some(color),
+        }]),
     };
     let client = CLIENT.get_or_init(Client::new).clone();
     thread::spawn(move || {